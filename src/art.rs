@@ -15,15 +15,32 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn drawCannonParticle(this: &Art, x: f32, y: f32, r: f32);
 
+    #[wasm_bindgen(method)]
+    pub fn drawTeleportParticle(this: &Art, x: f32, y: f32, r: f32);
+
     #[wasm_bindgen(method)]
     pub fn drawSniperParticle(this: &Art, x: f32, y: f32, x2: f32, y2: f32);
 
     #[wasm_bindgen(method)]
-    pub fn drawCreep(this: &Art, x: f32, y: f32, health_percentage: f32, kind: CreepKind);
+    pub fn drawLaserBeam(this: &Art, x: f32, y: f32, x2: f32, y2: f32, intensity: f32);
+
+    #[wasm_bindgen(method)]
+    pub fn drawCreep(
+        this: &Art,
+        x: f32,
+        y: f32,
+        health_percentage: f32,
+        shield_percentage: f32,
+        burrowed: bool,
+        kind: CreepKind,
+    );
 
     #[wasm_bindgen(method)]
     pub fn drawMap(this: &Art, width: f32, height: f32);
 
+    #[wasm_bindgen(method)]
+    pub fn setAlpha(this: &Art, alpha: f32);
+
     #[wasm_bindgen(method)]
     pub fn startCreepPath(this: &Art, x: f32, y: f32, time: f32);
 
@@ -42,3 +59,105 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn clear(this: &Art);
 }
+
+/// The drawing calls used by `Game::draw`. `Art` forwards them to the js canvas,
+/// other implementations (e.g. `SvgRenderer`) allow drawing the game natively.
+pub trait Renderer {
+    fn draw_turret(&self, x: f32, y: f32, rotation: f32, size: f32, kind: i32);
+    fn draw_particle(&self, x: f32, y: f32);
+    fn draw_cannon_particle(&self, x: f32, y: f32, r: f32);
+    fn draw_teleport_particle(&self, x: f32, y: f32, r: f32);
+    fn draw_sniper_particle(&self, x: f32, y: f32, x2: f32, y2: f32);
+    fn draw_laser_beam(&self, x: f32, y: f32, x2: f32, y2: f32, intensity: f32); // 0.0 to 1.0
+    fn draw_creep(
+        &self,
+        x: f32,
+        y: f32,
+        health_percentage: f32,
+        shield_percentage: f32, // 0 for creeps without shield
+        burrowed: bool,
+        kind: CreepKind,
+    );
+    fn draw_map(&self, width: f32, height: f32);
+    fn set_alpha(&self, alpha: f32); // for everything drawn afterwards, 1.0 = opaque
+    fn start_creep_path(&self, x: f32, y: f32, time: f32);
+    fn draw_creep_path_line(&self, x: f32, y: f32);
+    fn end_creep_path(&self);
+    fn draw_creep_spawn(&self, x: f32, y: f32, size: f32);
+    fn draw_creep_goal(&self, x: f32, y: f32, size: f32);
+    fn clear(&self);
+
+    // the js frontend only shows the range of the selected turret, so drawing
+    // ranges is optional
+    fn draw_turret_range(&self, _x: f32, _y: f32, _range: f32) {}
+}
+
+impl Renderer for Art {
+    fn draw_turret(&self, x: f32, y: f32, rotation: f32, size: f32, kind: i32) {
+        self.drawTurret(x, y, rotation, size, kind);
+    }
+
+    fn draw_particle(&self, x: f32, y: f32) {
+        self.drawParticle(x, y);
+    }
+
+    fn draw_cannon_particle(&self, x: f32, y: f32, r: f32) {
+        self.drawCannonParticle(x, y, r);
+    }
+
+    fn draw_teleport_particle(&self, x: f32, y: f32, r: f32) {
+        self.drawTeleportParticle(x, y, r);
+    }
+
+    fn draw_sniper_particle(&self, x: f32, y: f32, x2: f32, y2: f32) {
+        self.drawSniperParticle(x, y, x2, y2);
+    }
+
+    fn draw_laser_beam(&self, x: f32, y: f32, x2: f32, y2: f32, intensity: f32) {
+        self.drawLaserBeam(x, y, x2, y2, intensity);
+    }
+
+    fn draw_creep(
+        &self,
+        x: f32,
+        y: f32,
+        health_percentage: f32,
+        shield_percentage: f32,
+        burrowed: bool,
+        kind: CreepKind,
+    ) {
+        self.drawCreep(x, y, health_percentage, shield_percentage, burrowed, kind);
+    }
+
+    fn draw_map(&self, width: f32, height: f32) {
+        self.drawMap(width, height);
+    }
+
+    fn set_alpha(&self, alpha: f32) {
+        self.setAlpha(alpha);
+    }
+
+    fn start_creep_path(&self, x: f32, y: f32, time: f32) {
+        self.startCreepPath(x, y, time);
+    }
+
+    fn draw_creep_path_line(&self, x: f32, y: f32) {
+        self.drawCreepPathLine(x, y);
+    }
+
+    fn end_creep_path(&self) {
+        self.endCreepPath();
+    }
+
+    fn draw_creep_spawn(&self, x: f32, y: f32, size: f32) {
+        self.drawCreepSpawn(x, y, size);
+    }
+
+    fn draw_creep_goal(&self, x: f32, y: f32, size: f32) {
+        self.drawCreepGoal(x, y, size);
+    }
+
+    fn clear(&self) {
+        Art::clear(self);
+    }
+}
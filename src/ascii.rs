@@ -0,0 +1,132 @@
+// Text representation of the board, e.g. for the terminal or for readable test assertions.
+//
+// Every cell is two characters wide:
+//   `. ` empty cell          `@ ` creep spawn        `G1` creep goal (1-based index)
+//   `+ ` cell on creep path  `c3` number of creeps in the cell (`c*` for more than 9)
+//   `B0` turret: kind (B)asic, (S)niper, (C)annon, (M)ulti, (F)reeze, (P)oison,
+//        (L)aser and level (0-9, a for 10)
+
+use crate::recycled_list::RecycledList;
+use crate::utils::{to_grid_position, GridPosition};
+use crate::{GamePhase, SpecificData, State, Turret};
+
+fn turret_cell(turret: &Turret) -> String {
+    let kind = match turret.specific_data {
+        SpecificData::Basic(_) => 'B',
+        SpecificData::Sniper(_) => 'S',
+        SpecificData::Cannon(_) => 'C',
+        SpecificData::Multi(_) => 'M',
+        SpecificData::Freeze(_) => 'F',
+        SpecificData::Poison(_) => 'P',
+        SpecificData::Laser(_) => 'L',
+    };
+    let level = char::from_digit(turret.general_data.level, 36).unwrap_or('?');
+    format!("{kind}{level}")
+}
+
+pub fn render_board(state: &State, turrets: &RecycledList<Turret>) -> String {
+    let nx = state.board_dimension_x as usize;
+    let ny = state.board_dimension_y as usize;
+    let mut cells = vec![vec![String::from(". "); nx]; ny];
+    let in_board = |pos: GridPosition| (pos.x as usize) < nx && (pos.y as usize) < ny;
+
+    for pos in state.creep_path.iter() {
+        let grid_pos = to_grid_position(*pos, state.cell_length);
+        if in_board(grid_pos) {
+            cells[grid_pos.y as usize][grid_pos.x as usize] = String::from("+ ");
+        }
+    }
+
+    for (i, goal) in state.creep_goals.iter().enumerate() {
+        if in_board(*goal) {
+            cells[goal.y as usize][goal.x as usize] = format!("G{}", i + 1);
+        }
+    }
+
+    if in_board(state.creep_spawn) {
+        cells[state.creep_spawn.y as usize][state.creep_spawn.x as usize] = String::from("@ ");
+    }
+
+    for turret in turrets.iter() {
+        let pos = turret.general_data.pos;
+        if in_board(pos) {
+            cells[pos.y as usize][pos.x as usize] = turret_cell(turret);
+        }
+    }
+
+    let mut creep_count = vec![vec![0; nx]; ny];
+    for creep in state.creeps.iter() {
+        if creep.pos.x < 0.0 || creep.pos.y < 0.0 {
+            continue;
+        }
+        let grid_pos = to_grid_position(creep.pos, state.cell_length);
+        if in_board(grid_pos) {
+            creep_count[grid_pos.y as usize][grid_pos.x as usize] += 1;
+        }
+    }
+    for (y, row) in creep_count.iter().enumerate() {
+        for (x, count) in row.iter().enumerate() {
+            if *count > 0 {
+                cells[y][x] = format!("c{}", char::from_digit(*count, 10).unwrap_or('*'));
+            }
+        }
+    }
+
+    cells
+        .iter()
+        .map(|row| row.concat().trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn render_status(state: &State) -> String {
+    let phase = match state.game_phase {
+        GamePhase::Building => "building",
+        GamePhase::Fighting => "fighting",
+    };
+    let wave = if state.endless {
+        format!(
+            "{} (endless, best {})",
+            state.current_level + 1,
+            state.highest_wave
+        )
+    } else {
+        format!("{}/{}", state.current_level + 1, state.max_level)
+    };
+    format!(
+        "gold: {} | health: {} | wave: {} | phase: {}",
+        state.gold, state.health, wave, phase
+    )
+}
+
+pub fn render(state: &State, turrets: &RecycledList<Turret>) -> String {
+    format!(
+        "{}\n{}\n",
+        render_board(state, turrets),
+        render_status(state)
+    )
+}
+
+#[test]
+fn test_render_board() {
+    let mut game = crate::Game::new();
+    game.state.board_dimension_x = 5;
+    game.state.board_dimension_y = 3;
+    game.state.creep_spawn = GridPosition { x: 0, y: 0 };
+    game.state.creep_goals = vec![GridPosition { x: 4, y: 0 }];
+    game.state.creep_path = crate::compute_creep_paths(&game.state, &game.turret_state).unwrap();
+    game.build_tower(75.0, 15.0, 1);
+    game.build_tower(75.0, 45.0, 0);
+
+    assert_eq!(
+        render(&game.state, &game.turret_state),
+        [
+            "@ + S0+ G1",
+            ". + B0+ .",
+            ". + + + .",
+            "gold: 100 | health: 10 | wave: 1/50 | phase: building",
+            "",
+        ]
+        .join("\n")
+    );
+}
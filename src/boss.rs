@@ -0,0 +1,98 @@
+use crate::{Creep, CreepKind, Shield};
+
+// every ability runs on its own cycle, counted from the spawn of the boss
+const SPEED_BURST_INTERVAL_IN_TICKS: u32 = 360;
+const SPEED_BURST_DURATION_IN_TICKS: u32 = 60;
+const SPEED_BURST_MULTIPLIER: f32 = 2.5;
+
+const MINION_INTERVAL_IN_TICKS: u32 = 300;
+const MINIONS_PER_SPAWN: u32 = 3;
+const MINION_HEALTH: f32 = 0.05; // of the boss' max health
+
+const SLOW_IMMUNITY_INTERVAL_IN_TICKS: u32 = 600;
+const SLOW_IMMUNITY_DURATION_IN_TICKS: u32 = 180;
+
+const SHIELD: f32 = 0.3; // of the boss' max health
+const SHIELD_REGENERATION_DELAY_IN_TICKS: u32 = 120;
+const SHIELD_REGENERATION_IN_TICKS: u32 = 600; // from empty to full
+
+#[derive(Clone, Copy, Default)]
+pub struct BossData {
+    ticks: u32,
+}
+
+impl BossData {
+    // returns true when minions should be spawned
+    pub fn tick(&mut self) -> bool {
+        self.ticks += 1;
+        self.ticks.is_multiple_of(MINION_INTERVAL_IN_TICKS)
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.ticks % SPEED_BURST_INTERVAL_IN_TICKS
+            >= SPEED_BURST_INTERVAL_IN_TICKS - SPEED_BURST_DURATION_IN_TICKS
+        {
+            SPEED_BURST_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    pub fn is_slow_immune(&self) -> bool {
+        self.ticks % SLOW_IMMUNITY_INTERVAL_IN_TICKS
+            >= SLOW_IMMUNITY_INTERVAL_IN_TICKS - SLOW_IMMUNITY_DURATION_IN_TICKS
+    }
+}
+
+pub fn create_boss_shield(max_health: f32) -> Shield {
+    Shield::new(
+        max_health * SHIELD,
+        SHIELD_REGENERATION_DELAY_IN_TICKS,
+        SHIELD_REGENERATION_IN_TICKS,
+    )
+}
+
+pub fn create_minions(boss: &Creep) -> Vec<Creep> {
+    (0..MINIONS_PER_SPAWN)
+        .map(|_| Creep {
+            health: boss.max_health * MINION_HEALTH,
+            max_health: boss.max_health * MINION_HEALTH,
+            speed: boss.speed * 1.2,
+            gold: u32::max(1, boss.gold / MINIONS_PER_SPAWN),
+            kind: CreepKind::Minion,
+            last_freeze_percent: 0.0,
+            max_freeze_percent: 0.0,
+            delta_speed: 0.0,
+            slow_speed_accumulated: 0.0,
+            boss: None,
+            shield: None,
+            poison: None,
+            leak_damage: CreepKind::Minion.default_leak_damage(),
+            ticks_alive: 0,
+            ..*boss
+        })
+        .collect()
+}
+
+#[test]
+fn test_boss_abilities() {
+    let mut boss = BossData::default();
+
+    let mut minion_spawns = 0;
+    let mut burst_ticks = 0;
+    let mut immune_ticks = 0;
+    for _ in 0..600 {
+        if boss.tick() {
+            minion_spawns += 1;
+        }
+        if boss.speed_multiplier() > 1.0 {
+            burst_ticks += 1;
+        }
+        if boss.is_slow_immune() {
+            immune_ticks += 1;
+        }
+    }
+    assert_eq!(minion_spawns, 2);
+    assert_eq!(burst_ticks, 60);
+    assert_eq!(immune_ticks, 180);
+}
@@ -3,6 +3,7 @@ use std::f32::consts::{PI, TAU};
 use wasm_bindgen::prelude::*;
 
 use crate::{
+    boss::{create_boss_shield, BossData},
     recycled_list::{RecycledList, RecycledListItem, RecycledListRef},
     utils::{distance, to_creep_position, FloatPosition, GridPosition},
     State,
@@ -22,6 +23,154 @@ pub enum CreepKind {
     Grouped,
     Speed,
     Big,
+    Boss,
+    Minion,
+    Healer,
+    Splitter,
+    Splitling, // released by a dying splitter
+    Shielded,
+    Stealth,     // only visible to towers in the range of a detector
+    Flying,      // flies straight from waypoint to waypoint, ignoring the maze
+    Emp,         // disables turrets around it from time to time
+    Enraging,    // gets faster the more health it has lost
+    Carrier,     // drops minions behind it while walking
+    Burrowing,   // can't be hit while it is under ground
+    Teleporting, // blinks forward along the creep path
+}
+
+impl CreepKind {
+    // lives lost when a creep of this kind reaches the last goal
+    pub fn default_leak_damage(self) -> f32 {
+        match self {
+            CreepKind::Grouped | CreepKind::Minion | CreepKind::Splitling => 0.5,
+            CreepKind::Big | CreepKind::Splitter => 2.0,
+            CreepKind::Boss => 5.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DamageType {
+    Kinetic,   // basic and multi
+    Piercing,  // sniper
+    Explosive, // cannon
+    Energy,    // laser
+}
+
+// healers restore health of the other creeps around them
+pub const HEALER_RANGE: f32 = 1.5; // tiles
+pub const HEALER_INTERVAL_IN_TICKS: u32 = 60;
+pub const HEALER_HEAL_PERCENT: f32 = 8.0; // of the healed creep's max health
+
+pub const SPLITTER_CHILDREN: u32 = 3;
+pub const SPLITTER_CHILD_HEALTH: f32 = 0.3; // of the splitter's max health
+
+pub const EMP_INTERVAL_IN_TICKS: u32 = 240;
+pub const EMP_RANGE: f32 = 2.0; // tiles
+pub const EMP_DURATION_IN_TICKS: u32 = 120;
+
+pub const CARRIER_DROP_INTERVAL_IN_TICKS: u32 = 120;
+pub const CARRIER_MAX_DROPS: u32 = 8;
+pub const CARRIER_MINION_HEALTH: f32 = 0.15; // of the carrier's max health
+
+pub const BURROW_INTERVAL_IN_TICKS: u32 = 300;
+pub const BURROW_DURATION_IN_TICKS: u32 = 120; // at the end of every interval
+
+pub const TELEPORT_INTERVAL_IN_TICKS: u32 = 240;
+pub const TELEPORT_CELLS: u32 = 3;
+pub const TELEPORTS_PER_LIFE: u32 = 3;
+
+pub const ENRAGE_MAX_BONUS: f32 = 1.5; // speed bonus at zero health, 1.0 = +100%
+
+pub const POISON_BONUS_MULTIPLIER: f32 = 1.5; // against Big creeps and bosses
+
+pub const SHIELDED_SHIELD: f32 = 0.5; // of the creep's max health
+pub const SHIELDED_REGENERATION_DELAY_IN_TICKS: u32 = 180;
+pub const SHIELDED_REGENERATION_IN_TICKS: u32 = 300; // from empty to full
+pub const SHIELD_WEAKNESS: DamageType = DamageType::Piercing;
+pub const SHIELD_WEAKNESS_MULTIPLIER: f32 = 2.0;
+
+// absorbs damage before the health, regenerates after some ticks without damage
+#[derive(Clone, Copy)]
+pub struct Shield {
+    pub value: f32,
+    pub max_value: f32,
+    regeneration_delay_in_ticks: u32,
+    regeneration_per_tick: f32,
+    ticks_since_damage: u32,
+}
+
+impl Shield {
+    pub fn new(
+        max_value: f32,
+        regeneration_delay_in_ticks: u32,
+        regeneration_in_ticks: u32,
+    ) -> Self {
+        Shield {
+            value: max_value,
+            max_value,
+            regeneration_delay_in_ticks,
+            regeneration_per_tick: max_value / regeneration_in_ticks as f32,
+            ticks_since_damage: 0,
+        }
+    }
+
+    pub fn for_kind(kind: CreepKind, max_health: f32) -> Option<Shield> {
+        match kind {
+            CreepKind::Shielded => Some(Shield::new(
+                max_health * SHIELDED_SHIELD,
+                SHIELDED_REGENERATION_DELAY_IN_TICKS,
+                SHIELDED_REGENERATION_IN_TICKS,
+            )),
+            CreepKind::Boss => Some(create_boss_shield(max_health)),
+            _ => None,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks_since_damage += 1;
+        if self.ticks_since_damage > self.regeneration_delay_in_ticks {
+            self.value = f32::min(self.max_value, self.value + self.regeneration_per_tick);
+        }
+    }
+
+    // returns the damage left for the health
+    pub fn absorb(&mut self, damage: f32, damage_type: DamageType) -> f32 {
+        self.ticks_since_damage = 0;
+        let multiplier = if damage_type == SHIELD_WEAKNESS {
+            SHIELD_WEAKNESS_MULTIPLIER
+        } else {
+            1.0
+        };
+        let absorbed = f32::min(self.value, damage * multiplier);
+        self.value -= absorbed;
+        damage - absorbed / multiplier
+    }
+}
+
+// carried by poison particles
+#[derive(Clone, Copy)]
+pub struct PoisonHit {
+    pub percent_per_second: f32, // of the creep's max health, per stack
+    pub duration_in_ticks: u32,
+    pub max_stacks: u32,
+}
+
+// damage over time, ignores shields
+#[derive(Clone, Copy)]
+pub struct Poison {
+    pub stacks: u32,
+    pub damage_per_stack: f32, // per tick
+    pub remaining_ticks: u32,
+}
+
+impl Poison {
+    // returns the damage of this tick
+    pub fn tick(&mut self) -> f32 {
+        self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+        self.stacks as f32 * self.damage_per_stack
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -37,6 +186,101 @@ pub struct Creep {
     pub max_freeze_percent: f32,
     pub delta_speed: f32,            //temporary variable
     pub slow_speed_accumulated: f32, // needs to be subtracted from speed to get current speed
+    pub boss: Option<BossData>,
+    pub shield: Option<Shield>,
+    pub poison: Option<Poison>,
+    pub visible: bool, // stealth creeps outside of detector range can't be targeted
+    pub leak_damage: f32,
+    pub ticks_alive: u32,
+}
+
+impl Creep {
+    pub fn is_flying(&self) -> bool {
+        matches!(self.kind, CreepKind::Flying)
+    }
+
+    // burrowed creeps keep walking, but can't be targeted or hit
+    pub fn is_burrowed(&self) -> bool {
+        matches!(self.kind, CreepKind::Burrowing)
+            && self.ticks_alive % BURROW_INTERVAL_IN_TICKS
+                >= BURROW_INTERVAL_IN_TICKS - BURROW_DURATION_IN_TICKS
+    }
+
+    pub fn teleports(&self) -> bool {
+        matches!(self.kind, CreepKind::Teleporting)
+            && self.ticks_alive.is_multiple_of(TELEPORT_INTERVAL_IN_TICKS)
+            && self.ticks_alive / TELEPORT_INTERVAL_IN_TICKS <= TELEPORTS_PER_LIFE
+    }
+
+    pub fn emits_pulse(&self) -> bool {
+        matches!(self.kind, CreepKind::Emp)
+            && self.ticks_alive.is_multiple_of(EMP_INTERVAL_IN_TICKS)
+    }
+
+    // slows reduce the base speed, boss bursts and enrage multiply what is left
+    pub fn current_speed(&self) -> f32 {
+        let mut speed = self.speed - self.slow_speed_accumulated;
+        if let Some(boss) = &self.boss {
+            speed *= boss.speed_multiplier();
+        }
+        if let CreepKind::Enraging = self.kind {
+            let missing_health = 1.0 - f32::max(0.0, self.health) / self.max_health;
+            speed *= 1.0 + ENRAGE_MAX_BONUS * missing_health;
+        }
+        speed
+    }
+
+    // creeps released when this creep is killed
+    pub fn on_death(&self) -> Vec<Creep> {
+        match self.kind {
+            CreepKind::Splitter => (0..SPLITTER_CHILDREN)
+                .map(|i| Creep {
+                    health: self.max_health * SPLITTER_CHILD_HEALTH,
+                    max_health: self.max_health * SPLITTER_CHILD_HEALTH,
+                    walking: WalkingProgress {
+                        // spread the children a bit, they continue where the splitter died
+                        progress_made: f32::max(0.0, self.walking.progress_made - i as f32 * 0.3),
+                        ..self.walking
+                    },
+                    speed: self.speed * 1.2,
+                    gold: u32::max(1, self.gold / SPLITTER_CHILDREN),
+                    kind: CreepKind::Splitling,
+                    poison: None,
+                    leak_damage: CreepKind::Splitling.default_leak_damage(),
+                    ..*self
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    // every hit adds a stack up to the cap of the turret and refreshes the duration
+    pub fn apply_poison(&mut self, hit: PoisonHit) {
+        let mut damage_per_stack = self.max_health * hit.percent_per_second / 100.0 / 60.0;
+        if matches!(self.kind, CreepKind::Big | CreepKind::Boss) {
+            damage_per_stack *= POISON_BONUS_MULTIPLIER;
+        }
+        self.poison = Some(match self.poison {
+            Some(poison) => Poison {
+                stacks: u32::max(poison.stacks, u32::min(poison.stacks + 1, hit.max_stacks)),
+                damage_per_stack: f32::max(poison.damage_per_stack, damage_per_stack),
+                remaining_ticks: hit.duration_in_ticks,
+            },
+            None => Poison {
+                stacks: 1,
+                damage_per_stack,
+                remaining_ticks: hit.duration_in_ticks,
+            },
+        });
+    }
+
+    pub fn take_damage(&mut self, damage: f32, damage_type: DamageType) {
+        let damage = match &mut self.shield {
+            Some(shield) => shield.absorb(damage, damage_type),
+            None => damage,
+        };
+        self.health -= damage;
+    }
 }
 
 pub trait HasCost {
@@ -223,6 +467,8 @@ impl FollowsTarget for DynamicBasicData {
                 damage: turret_data.damage * turret_data.damage_multiplier / 100.0,
                 speed: turret_data.projectile_speed * state.cell_length / 60.0,
                 explosion_radius: 0.0,
+                damage_type: DamageType::Kinetic,
+                poison: None,
             });
         }
     }
@@ -428,10 +674,9 @@ impl FollowsTarget for DynamicSniperData {
                 lifetime_in_ticks: 5,
             });
 
-            mut_target_creep.health -= turret_data.damage;
+            mut_target_creep.take_damage(turret_data.damage, DamageType::Piercing);
             if mut_target_creep.health <= 0.0 {
-                state.gold += mut_target_creep.gold;
-                state.creeps.remove(self.target);
+                state.kill_creep(self.target);
             }
         }
     }
@@ -598,6 +843,10 @@ impl FollowsTarget for DynamicCannonData {
         CANNON[level as usize].range
     }
 
+    fn hits_air(&self) -> bool {
+        false
+    }
+
     fn blast(&mut self, general_data: &mut GeneralData, state: &mut State, is_in_aim: bool) {
         let turret_data = &CANNON[general_data.level as usize];
         if is_in_aim
@@ -617,6 +866,8 @@ impl FollowsTarget for DynamicCannonData {
                 damage: turret_data.damage,
                 speed: turret_data.projectile_speed * state.cell_length / 60.0,
                 explosion_radius: turret_data.explosion_radius,
+                damage_type: DamageType::Explosive,
+                poison: None,
             });
         }
     }
@@ -841,6 +1092,405 @@ impl FollowsTarget for DynamicMultiData {
     }
 }
 
+#[derive(Copy, Clone)]
+pub struct StaticPoisonData {
+    pub range: f32, // tiles
+    pub damage: f32,
+    pub poison_percent: f32, // of the creep's max health per second and stack
+    pub poison_duration: f32, // s
+    pub max_stacks: u32,
+    pub attack_speed: f32,     // attacks/s
+    pub rotation_speed: f32,   // deg/s
+    pub projectile_speed: f32, // tiles/s
+    pub cost: u32,
+}
+
+impl HasCost for StaticPoisonData {
+    fn get_cost(&self) -> u32 {
+        self.cost
+    }
+}
+
+pub const POISON: [StaticPoisonData; 11] = [
+    // 0
+    StaticPoisonData {
+        range: 2.2,
+        damage: 2.0,
+        poison_percent: 0.5,
+        poison_duration: 3.0,
+        max_stacks: 3,
+        attack_speed: 0.8,
+        rotation_speed: 90.0,
+        projectile_speed: 2.5,
+        cost: 30,
+    },
+    // 1
+    StaticPoisonData {
+        range: 2.3,
+        damage: 3.0,
+        poison_percent: 0.6,
+        poison_duration: 3.0,
+        max_stacks: 3,
+        attack_speed: 0.85,
+        rotation_speed: 95.0,
+        projectile_speed: 2.6,
+        cost: 60,
+    },
+    // 2
+    StaticPoisonData {
+        range: 2.4,
+        damage: 4.0,
+        poison_percent: 0.7,
+        poison_duration: 3.5,
+        max_stacks: 4,
+        attack_speed: 0.9,
+        rotation_speed: 100.0,
+        projectile_speed: 2.7,
+        cost: 90,
+    },
+    // 3
+    StaticPoisonData {
+        range: 2.5,
+        damage: 5.0,
+        poison_percent: 0.8,
+        poison_duration: 3.5,
+        max_stacks: 4,
+        attack_speed: 0.95,
+        rotation_speed: 105.0,
+        projectile_speed: 2.8,
+        cost: 130,
+    },
+    // 4
+    StaticPoisonData {
+        range: 2.5,
+        damage: 7.0,
+        poison_percent: 0.9,
+        poison_duration: 4.0,
+        max_stacks: 5,
+        attack_speed: 1.0,
+        rotation_speed: 110.0,
+        projectile_speed: 2.9,
+        cost: 180,
+    },
+    // 5
+    StaticPoisonData {
+        range: 2.6,
+        damage: 9.0,
+        poison_percent: 1.0,
+        poison_duration: 4.0,
+        max_stacks: 5,
+        attack_speed: 1.05,
+        rotation_speed: 120.0,
+        projectile_speed: 3.0,
+        cost: 260,
+    },
+    // 6
+    StaticPoisonData {
+        range: 2.6,
+        damage: 11.0,
+        poison_percent: 1.1,
+        poison_duration: 4.5,
+        max_stacks: 6,
+        attack_speed: 1.1,
+        rotation_speed: 130.0,
+        projectile_speed: 3.1,
+        cost: 380,
+    },
+    // 7
+    StaticPoisonData {
+        range: 2.7,
+        damage: 14.0,
+        poison_percent: 1.2,
+        poison_duration: 4.5,
+        max_stacks: 6,
+        attack_speed: 1.15,
+        rotation_speed: 140.0,
+        projectile_speed: 3.2,
+        cost: 550,
+    },
+    // 8
+    StaticPoisonData {
+        range: 2.8,
+        damage: 17.0,
+        poison_percent: 1.35,
+        poison_duration: 5.0,
+        max_stacks: 7,
+        attack_speed: 1.2,
+        rotation_speed: 150.0,
+        projectile_speed: 3.3,
+        cost: 800,
+    },
+    // 9
+    StaticPoisonData {
+        range: 2.9,
+        damage: 21.0,
+        poison_percent: 1.5,
+        poison_duration: 5.0,
+        max_stacks: 7,
+        attack_speed: 1.25,
+        rotation_speed: 160.0,
+        projectile_speed: 3.4,
+        cost: 1200,
+    },
+    // 10
+    StaticPoisonData {
+        range: 3.0,
+        damage: 25.0,
+        poison_percent: 1.7,
+        poison_duration: 5.5,
+        max_stacks: 8,
+        attack_speed: 1.3,
+        rotation_speed: 170.0,
+        projectile_speed: 3.5,
+        cost: 1800,
+    },
+];
+
+#[derive(Copy, Clone)]
+pub struct DynamicPoisonData {
+    pub rotation: f32, // orientation/angle in RAD
+    pub target: RecycledListRef,
+}
+
+impl FollowsTarget for DynamicPoisonData {
+    fn get_target(&self) -> RecycledListRef {
+        self.target
+    }
+
+    fn set_target(&mut self, target: RecycledListRef) {
+        self.target = target;
+    }
+
+    fn get_rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn set_rotation(&mut self, new_rotation: f32) {
+        self.rotation = new_rotation;
+    }
+
+    fn get_rotation_speed(&self, level: u32) -> f32 {
+        POISON[level as usize].rotation_speed
+    }
+
+    fn get_range(&self, level: u32) -> f32 {
+        POISON[level as usize].range
+    }
+
+    fn blast(&mut self, general_data: &mut GeneralData, state: &mut State, is_in_aim: bool) {
+        let turret_data = &POISON[general_data.level as usize];
+        if is_in_aim
+            && state.tick > general_data.last_shot + (60.0 / turret_data.attack_speed) as u32
+        {
+            // the turret position is the start of the barrel, where particles are emitted
+            let x = (general_data.pos.x as f32 + 0.5) * state.cell_length
+                + state.cell_length / 2.0 * self.rotation.cos();
+            let y = (general_data.pos.y as f32 + 0.5) * state.cell_length
+                + state.cell_length / 2.0 * self.rotation.sin();
+            let turret_pos = FloatPosition { x, y };
+
+            general_data.last_shot = state.tick;
+            state.particles.add(Particle {
+                pos: turret_pos,
+                target: self.target,
+                damage: turret_data.damage,
+                speed: turret_data.projectile_speed * state.cell_length / 60.0,
+                explosion_radius: 0.0,
+                damage_type: DamageType::Kinetic,
+                poison: Some(PoisonHit {
+                    percent_per_second: turret_data.poison_percent,
+                    duration_in_ticks: (turret_data.poison_duration * 60.0) as u32,
+                    max_stacks: turret_data.max_stacks,
+                }),
+            });
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct StaticLaserData {
+    pub range: f32,  // tiles
+    pub damage: f32, // per second, before the ramp up
+    pub max_multiplier: f32,
+    pub ramp_time: f32, // s on the same target until `max_multiplier` is reached
+    pub rotation_speed: f32, // deg/s
+    pub cost: u32,
+}
+
+impl HasCost for StaticLaserData {
+    fn get_cost(&self) -> u32 {
+        self.cost
+    }
+}
+
+pub const LASER: [StaticLaserData; 11] = [
+    // 0
+    StaticLaserData {
+        range: 2.0,
+        damage: 6.0,
+        max_multiplier: 3.0,
+        ramp_time: 3.0,
+        rotation_speed: 60.0,
+        cost: 40,
+    },
+    // 1
+    StaticLaserData {
+        range: 2.1,
+        damage: 10.0,
+        max_multiplier: 3.0,
+        ramp_time: 3.0,
+        rotation_speed: 65.0,
+        cost: 70,
+    },
+    // 2
+    StaticLaserData {
+        range: 2.2,
+        damage: 15.0,
+        max_multiplier: 3.1,
+        ramp_time: 2.9,
+        rotation_speed: 70.0,
+        cost: 110,
+    },
+    // 3
+    StaticLaserData {
+        range: 2.3,
+        damage: 21.0,
+        max_multiplier: 3.2,
+        ramp_time: 2.8,
+        rotation_speed: 75.0,
+        cost: 160,
+    },
+    // 4
+    StaticLaserData {
+        range: 2.4,
+        damage: 28.0,
+        max_multiplier: 3.3,
+        ramp_time: 2.7,
+        rotation_speed: 80.0,
+        cost: 230,
+    },
+    // 5
+    StaticLaserData {
+        range: 2.5,
+        damage: 36.0,
+        max_multiplier: 3.4,
+        ramp_time: 2.6,
+        rotation_speed: 85.0,
+        cost: 330,
+    },
+    // 6
+    StaticLaserData {
+        range: 2.6,
+        damage: 45.0,
+        max_multiplier: 3.5,
+        ramp_time: 2.5,
+        rotation_speed: 90.0,
+        cost: 480,
+    },
+    // 7
+    StaticLaserData {
+        range: 2.7,
+        damage: 55.0,
+        max_multiplier: 3.6,
+        ramp_time: 2.4,
+        rotation_speed: 100.0,
+        cost: 700,
+    },
+    // 8
+    StaticLaserData {
+        range: 2.8,
+        damage: 66.0,
+        max_multiplier: 3.7,
+        ramp_time: 2.3,
+        rotation_speed: 105.0,
+        cost: 1000,
+    },
+    // 9
+    StaticLaserData {
+        range: 2.9,
+        damage: 78.0,
+        max_multiplier: 3.85,
+        ramp_time: 2.15,
+        rotation_speed: 110.0,
+        cost: 1500,
+    },
+    // 10
+    StaticLaserData {
+        range: 3.0,
+        damage: 90.0,
+        max_multiplier: 4.0,
+        ramp_time: 2.0,
+        rotation_speed: 120.0,
+        cost: 2200,
+    },
+];
+
+#[derive(Copy, Clone)]
+pub struct DynamicLaserData {
+    pub rotation: f32, // orientation/angle in RAD
+    pub target: RecycledListRef,
+    pub beam_ticks: u32, // on the current target, see StaticLaserData::ramp_time
+    pub firing: bool,
+}
+
+impl DynamicLaserData {
+    // the ramp up only builds while the beam is on
+    pub fn stop(&mut self) {
+        self.beam_ticks = 0;
+        self.firing = false;
+    }
+
+    pub fn damage_multiplier(&self, level: u32) -> f32 {
+        let turret_data = &LASER[level as usize];
+        let ramp = f32::min(1.0, self.beam_ticks as f32 / (turret_data.ramp_time * 60.0));
+        1.0 + (turret_data.max_multiplier - 1.0) * ramp
+    }
+}
+
+impl FollowsTarget for DynamicLaserData {
+    fn get_target(&self) -> RecycledListRef {
+        self.target
+    }
+
+    fn set_target(&mut self, target: RecycledListRef) {
+        self.stop();
+        self.target = target;
+    }
+
+    fn get_rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn set_rotation(&mut self, new_rotation: f32) {
+        self.rotation = new_rotation;
+    }
+
+    fn get_rotation_speed(&self, level: u32) -> f32 {
+        LASER[level as usize].rotation_speed
+    }
+
+    fn get_range(&self, level: u32) -> f32 {
+        LASER[level as usize].range
+    }
+
+    fn blast(&mut self, general_data: &mut GeneralData, state: &mut State, is_in_aim: bool) {
+        // once locked on, the beam follows the target until set_target resets it
+        if !is_in_aim && !self.firing {
+            return;
+        }
+
+        self.firing = true;
+        self.beam_ticks += 1;
+        let damage = LASER[general_data.level as usize].damage / 60.0
+            * self.damage_multiplier(general_data.level);
+        let target_creep = state.creeps.get_mut(self.target).unwrap();
+        target_creep.take_damage(damage, DamageType::Energy);
+        if target_creep.health <= 0.0 {
+            state.kill_creep(self.target);
+        }
+    }
+}
+
 #[derive(Copy, Clone, Default)]
 pub struct StaticFreezeData {
     pub range: f32, // tiles
@@ -969,16 +1619,39 @@ pub enum SpecificData {
     Cannon(DynamicCannonData),
     Multi(DynamicMultiData),
     Freeze(StaticFreezeData),
+    Poison(DynamicPoisonData),
+    Laser(DynamicLaserData),
 }
 
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TargetingMode {
+    Nearest, // keeps its target until it leaves the range
+    First,   // furthest along the creep path
+    Last,
+    Strongest,
+    Weakest,
+    Healer, // healers first, then like `First`
+}
+
+// detectors reveal stealth creeps within the range of their tower
+pub const DETECTOR_COST: u32 = 60;
+
 #[derive(Copy, Clone)]
 pub struct GeneralData {
     pub pos: GridPosition,
     pub last_shot: u32,
     pub level: u32,
+    pub targeting: TargetingMode,
+    pub detector: bool,
+    pub disabled_until: u32, // tick, see EMP creeps
 }
 
 impl GeneralData {
+    pub fn is_disabled(&self, tick: u32) -> bool {
+        tick < self.disabled_until
+    }
+
     pub fn get_float_pos(&self, cell_length: f32) -> FloatPosition {
         to_creep_position(self.pos, cell_length)
     }
@@ -990,17 +1663,52 @@ pub struct Turret {
     pub specific_data: SpecificData,
 }
 
+fn is_targetable(creep: &Creep, hits_air: bool) -> bool {
+    creep.visible && !creep.is_burrowed() && (hits_air || !creep.is_flying())
+}
+
 fn find_nearest_creep(
     creeps: &RecycledList<Creep>,
     turret_pos: FloatPosition,
     turret_range: f32,
+    hits_air: bool,
 ) -> Option<&RecycledListItem<Creep>> {
-    return creeps
+    creeps
         .enumerate()
+        .filter(|creep_item| is_targetable(&creep_item.data, hits_air))
         .map(|creep_item| (distance(creep_item.data.pos, turret_pos), creep_item))
         .filter(|(d, _item_ref)| *d < turret_range)
         .min_by_key(|(d, _item_ref)| (*d * 100.0) as i32)
-        .map(|x| x.1);
+        .map(|x| x.1)
+}
+
+fn find_target(
+    creeps: &RecycledList<Creep>,
+    turret_pos: FloatPosition,
+    turret_range: f32,
+    targeting: TargetingMode,
+    hits_air: bool,
+) -> Option<RecycledListRef> {
+    let walked = |creep: &Creep| creep.walking.current_goal as f32 + creep.walking.progress_made;
+    let is_healer = |creep: &Creep| matches!(creep.kind, CreepKind::Healer);
+    let in_range = creeps.enumerate().filter(|creep_item| {
+        is_targetable(&creep_item.data, hits_air)
+            && distance(creep_item.data.pos, turret_pos) < turret_range
+    });
+
+    let target = match targeting {
+        TargetingMode::Nearest => find_nearest_creep(creeps, turret_pos, turret_range, hits_air),
+        TargetingMode::First => in_range.max_by(|a, b| walked(&a.data).total_cmp(&walked(&b.data))),
+        TargetingMode::Last => in_range.min_by(|a, b| walked(&a.data).total_cmp(&walked(&b.data))),
+        TargetingMode::Strongest => in_range.max_by(|a, b| a.data.health.total_cmp(&b.data.health)),
+        TargetingMode::Weakest => in_range.min_by(|a, b| a.data.health.total_cmp(&b.data.health)),
+        TargetingMode::Healer => in_range.max_by(|a, b| {
+            is_healer(&a.data)
+                .cmp(&is_healer(&b.data))
+                .then(walked(&a.data).total_cmp(&walked(&b.data)))
+        }),
+    };
+    target.map(|creep_item| creep_item.item_ref)
 }
 
 pub trait FollowsTarget {
@@ -1011,6 +1719,10 @@ pub trait FollowsTarget {
     fn get_rotation_speed(&self, level: u32) -> f32;
     fn get_range(&self, level: u32) -> f32;
     fn blast(&mut self, general_data: &mut GeneralData, state: &mut State, is_in_aim: bool);
+
+    fn hits_air(&self) -> bool {
+        true
+    }
 }
 
 pub fn update_tower(
@@ -1020,20 +1732,29 @@ pub fn update_tower(
 ) {
     let tower_pos = to_creep_position(general_data.pos, state.cell_length);
 
-    // find target
-    let target = state.creeps.get(specific.get_target());
+    // find target, every tick unless the tower sticks to the nearest creep
+    let target = state
+        .creeps
+        .get(specific.get_target())
+        .filter(|creep| is_targetable(creep, specific.hits_air()));
     let level = general_data.level;
-    if target.is_none() {
-        match find_nearest_creep(
+    if target.is_none() || general_data.targeting != TargetingMode::Nearest {
+        match find_target(
             &state.creeps,
             tower_pos,
             specific.get_range(level) * state.cell_length,
+            general_data.targeting,
+            specific.hits_air(),
         ) {
-            Some(creep) => {
-                specific.set_target(creep.item_ref);
+            Some(creep_ref) if target.is_none() || creep_ref != specific.get_target() => {
+                specific.set_target(creep_ref);
                 return update_tower(general_data, specific, state);
             }
-            None => return,
+            Some(_) => {}
+            None => {
+                specific.set_target(RecycledListRef::null_ref());
+                return;
+            }
         }
     }
 
@@ -1056,21 +1777,56 @@ pub fn update_tower(
         rotation_diff += TAU;
     }
 
-    specific.set_rotation(
-        specific.get_rotation()
-            + rotation_diff.signum()
-                * f32::min(
-                    specific.get_rotation_speed(level).to_radians() / 60.0,
-                    f32::abs(rotation_diff),
-                ),
+    let rotation_step = rotation_diff.signum()
+        * f32::min(
+            specific.get_rotation_speed(level).to_radians() / 60.0,
+            f32::abs(rotation_diff),
+        );
+    specific.set_rotation(specific.get_rotation() + rotation_step);
+
+    // aimed after this tick's rotation, so moving creeps can be caught up with
+    specific.blast(
+        general_data,
+        state,
+        (rotation_diff - rotation_step).abs() < 0.01,
     );
-
-    specific.blast(general_data, state, rotation_diff.abs() < 0.01);
 }
 
 impl Turret {
+    // in tiles
+    pub fn range(&self) -> f32 {
+        let level = self.general_data.level;
+        match &self.specific_data {
+            SpecificData::Basic(d) => d.get_range(level),
+            SpecificData::Sniper(d) => d.get_range(level),
+            SpecificData::Cannon(d) => d.get_range(level),
+            SpecificData::Multi(d) => d.get_range(level),
+            SpecificData::Freeze(d) => d.get_range(level),
+            SpecificData::Poison(d) => d.get_range(level),
+            SpecificData::Laser(d) => d.get_range(level),
+        }
+    }
+
+    pub fn hits_air(&self) -> bool {
+        match &self.specific_data {
+            SpecificData::Basic(d) => d.hits_air(),
+            SpecificData::Sniper(d) => d.hits_air(),
+            SpecificData::Cannon(d) => d.hits_air(),
+            SpecificData::Multi(d) => d.hits_air(),
+            SpecificData::Freeze(_) => false,
+            SpecificData::Poison(d) => d.hits_air(),
+            SpecificData::Laser(d) => d.hits_air(),
+        }
+    }
+
     pub fn tick(&mut self, state: &mut State) {
         let general_data = &mut self.general_data;
+        if general_data.is_disabled(state.tick) {
+            if let SpecificData::Laser(laser) = &mut self.specific_data {
+                laser.stop();
+            }
+            return;
+        }
 
         match &mut self.specific_data {
             SpecificData::Basic(specific_data) => update_tower(general_data, specific_data, state),
@@ -1078,6 +1834,8 @@ impl Turret {
             SpecificData::Cannon(specific_data) => update_tower(general_data, specific_data, state),
             SpecificData::Multi(specific_data) => update_tower(general_data, specific_data, state),
             SpecificData::Freeze(_specific_data) => {}
+            SpecificData::Poison(specific_data) => update_tower(general_data, specific_data, state),
+            SpecificData::Laser(specific_data) => update_tower(general_data, specific_data, state),
         }
     }
 }
@@ -1091,6 +1849,8 @@ pub struct Particle {
     pub damage: f32,
     pub speed: f32, // pixel per tick
     pub explosion_radius: f32,
+    pub damage_type: DamageType,
+    pub poison: Option<PoisonHit>,
 }
 
 pub trait ParticleWithLifetime {
@@ -1132,6 +1892,25 @@ impl ParticleWithLifetime for CannonParticle {
     }
 }
 
+pub const TELEPORT_EFFECT_LIFETIME_IN_TICKS: u32 = 20;
+
+// drawn where a teleporting creep left and where it arrived
+#[derive(Clone, Copy)]
+pub struct TeleportParticle {
+    pub pos: FloatPosition,
+    pub lifetime_in_ticks: u32, // delete at 0
+}
+
+impl ParticleWithLifetime for TeleportParticle {
+    fn lifetime_in_ticks(&self) -> u32 {
+        self.lifetime_in_ticks
+    }
+
+    fn decrement_lifetime(&mut self) {
+        self.lifetime_in_ticks -= 1
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct MultiParticle {
     pub pos: FloatPosition,
@@ -1150,3 +1929,84 @@ impl ParticleWithLifetime for MultiParticle {
         self.lifetime_in_ticks -= 1
     }
 }
+
+#[test]
+fn test_targeting_modes() {
+    let mut game = crate::Game::start_test_wave(
+        "group kind=Big count=1 health=200 bounty=1\n\
+         group kind=Healer count=1 health=50 bounty=1 parallel=true delay=30",
+    );
+    game.update_until(|game| game.state.creeps.iter().count() == 2);
+
+    let creeps = &game.state.creeps;
+    let pos = creeps.iter().last().unwrap().pos;
+    let target = |targeting| {
+        let target = find_target(creeps, pos, 1000.0, targeting, true).unwrap();
+        creeps.get(target).unwrap().kind
+    };
+    assert!(matches!(target(TargetingMode::First), CreepKind::Big));
+    assert!(matches!(target(TargetingMode::Last), CreepKind::Healer));
+    assert!(matches!(target(TargetingMode::Strongest), CreepKind::Big));
+    assert!(matches!(target(TargetingMode::Weakest), CreepKind::Healer));
+    assert!(matches!(target(TargetingMode::Nearest), CreepKind::Healer));
+    assert!(matches!(target(TargetingMode::Healer), CreepKind::Healer));
+}
+
+#[test]
+fn test_shield() {
+    let mut shield = Shield::new(100.0, 10, 50);
+    assert_eq!(shield.absorb(30.0, DamageType::Kinetic), 0.0);
+    assert_eq!(shield.value, 70.0);
+    assert_eq!(shield.absorb(50.0, SHIELD_WEAKNESS), 15.0);
+    assert_eq!(shield.value, 0.0);
+
+    for _ in 0..10 {
+        shield.tick();
+    }
+    assert_eq!(shield.value, 0.0);
+    shield.tick();
+    assert_eq!(shield.value, 2.0);
+}
+
+#[test]
+fn test_enrage() {
+    let mut game = crate::Game::start_test_wave("group kind=Enraging count=1 health=100 bounty=1");
+    game.update_until(|game| !game.state.creeps.is_empty());
+
+    let creep = game.state.creeps.iter_mut().next().unwrap();
+    assert_eq!(creep.current_speed(), creep.speed);
+    creep.health = 50.0;
+    assert_eq!(creep.current_speed(), creep.speed * 1.75);
+    creep.slow_speed_accumulated = creep.speed / 2.0;
+    assert_eq!(creep.current_speed(), creep.speed / 2.0 * 1.75);
+}
+
+#[test]
+fn test_poison() {
+    let mut game = crate::Game::start_test_wave("group kind=Big count=1 health=6000 bounty=1");
+    game.update_until(|game| !game.state.creeps.is_empty());
+
+    let hit = PoisonHit {
+        percent_per_second: 1.0,
+        duration_in_ticks: 2,
+        max_stacks: 3,
+    };
+    let creep = game.state.creeps.iter_mut().next().unwrap();
+    for _ in 0..5 {
+        creep.apply_poison(hit);
+    }
+    let poison = creep.poison.unwrap();
+    assert_eq!(poison.stacks, 3);
+    // 1% of 6000 per second, with the bonus against big creeps
+    assert_eq!(
+        poison.damage_per_stack,
+        60.0 / 60.0 * POISON_BONUS_MULTIPLIER
+    );
+
+    let health = creep.health;
+    game.update_state();
+    let creep = game.state.creeps.iter().next().unwrap();
+    assert_eq!(creep.health, health - 3.0 * POISON_BONUS_MULTIPLIER);
+    game.update_state();
+    assert!(game.state.creeps.iter().next().unwrap().poison.is_none());
+}
@@ -0,0 +1,47 @@
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{utils::FloatPosition, CreepKind};
+
+// events are dropped if nobody takes them, oldest first
+const MAX_EVENTS: usize = 256;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameEventKind {
+    CreepLeaked,   // value: lives lost, fractions add up
+    LivesRegained, // value: lives regained at a milestone wave
+    EmpPulse,      // value: radius in tiles
+    TeleportOut,   // value: cells skipped, followed by a `TeleportIn` at the destination
+    TeleportIn,    // value: cells skipped
+}
+
+/// Something that happened during `update_state`, e.g. for sounds or floating texts.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct GameEvent {
+    pub kind: GameEventKind,
+    pub tick: u32,
+    pub pos: FloatPosition,
+    pub creep_kind: Option<CreepKind>,
+    pub value: f32,
+}
+
+#[derive(Clone, Default)]
+pub struct EventQueue {
+    events: VecDeque<GameEvent>,
+}
+
+impl EventQueue {
+    pub fn push(&mut self, event: GameEvent) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn take(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events).into()
+    }
+}
@@ -1,9 +1,10 @@
 use crate::recycled_list::RecycledListRef;
-use crate::utils::{to_float_position, FloatPosition};
+use crate::spawn::{Spawn, SpawnPattern, Wave};
+use crate::utils::{distance, to_float_position, FloatPosition};
 use crate::{
-    DynamicBasicData, DynamicCannonData, DynamicMultiData, DynamicSniperData, FollowsTarget,
-    GamePhase, HasCost, SpecificData, State, StaticFreezeData, Turret, BASIC, CANNON, FREEZE,
-    MULTI, SNIPER,
+    Creep, CreepKind, DynamicBasicData, DynamicCannonData, DynamicLaserData, DynamicMultiData,
+    DynamicPoisonData, DynamicSniperData, GamePhase, HasCost, SpecificData, State,
+    StaticFreezeData, TargetingMode, Turret, BASIC, CANNON, FREEZE, LASER, MULTI, POISON, SNIPER,
 };
 use wasm_bindgen::prelude::*;
 
@@ -14,6 +15,10 @@ pub struct ExternalTurret {
     pub rotation: f32, // orientation/angle in RAD
     pub range: f32,
     pub kind: i32,
+    pub targeting: TargetingMode,
+    pub detector: bool,
+    pub hits_air: bool,
+    pub disabled: bool, // by an EMP creep
 }
 
 pub fn to_external_turret(turret: &Turret, state: &State) -> ExternalTurret {
@@ -25,26 +30,28 @@ pub fn to_external_turret(turret: &Turret, state: &State) -> ExternalTurret {
             SpecificData::Cannon(d) => d.rotation,
             SpecificData::Multi(d) => d.rotation,
             SpecificData::Freeze(_) => 0.0,
+            SpecificData::Poison(d) => d.rotation,
+            SpecificData::Laser(d) => d.rotation,
         },
-        range: match &turret.specific_data {
-            SpecificData::Basic(d) => d.get_range(turret.general_data.level) * state.cell_length,
-            SpecificData::Sniper(d) => d.get_range(turret.general_data.level) * state.cell_length,
-            SpecificData::Cannon(d) => d.get_range(turret.general_data.level) * state.cell_length,
-            SpecificData::Multi(d) => d.get_range(turret.general_data.level) * state.cell_length,
-            SpecificData::Freeze(d) => d.get_range(turret.general_data.level) * state.cell_length,
-        },
+        range: turret.range() * state.cell_length,
         kind: match &turret.specific_data {
             SpecificData::Basic(_) => 0,
             SpecificData::Sniper(_) => 1,
             SpecificData::Cannon(_) => 2,
             SpecificData::Multi(_) => 3,
             SpecificData::Freeze(_) => 4,
+            SpecificData::Poison(_) => 5,
+            SpecificData::Laser(_) => 6,
         },
+        targeting: turret.general_data.targeting,
+        detector: turret.general_data.detector,
+        hits_air: turret.hits_air(),
+        disabled: turret.general_data.is_disabled(state.tick),
     }
 }
 
 pub trait HasStats {
-    fn stats(&self, level: u32) -> Vec<Stat>;
+    fn stats(level: u32) -> Vec<Stat>;
 }
 
 fn get_cost<T: HasCost>(arr: &[T], level: usize) -> f32 {
@@ -61,7 +68,7 @@ fn get_cost<T: HasCost>(arr: &[T], level: usize) -> f32 {
 }
 
 impl HasStats for DynamicBasicData {
-    fn stats(&self, level: u32) -> Vec<Stat> {
+    fn stats(level: u32) -> Vec<Stat> {
         let level = level as usize;
         if level >= BASIC.len() {
             return vec![];
@@ -69,46 +76,46 @@ impl HasStats for DynamicBasicData {
 
         vec![
             Stat {
-                key: String::from("Level"),
+                id: StatId::Level,
                 value: (level + 1) as f32,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Range"),
+                id: StatId::Range,
                 value: BASIC[level].range,
-                unit: String::from("tiles"),
+                unit: StatUnit::Tiles,
             },
             Stat {
-                key: String::from("Damage"),
+                id: StatId::Damage,
                 value: BASIC[level].damage,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Attack speed"),
+                id: StatId::AttackSpeed,
                 value: BASIC[level].attack_speed,
-                unit: String::from("/s"),
+                unit: StatUnit::PerSecond,
             },
             Stat {
-                key: String::from("Rotation speed"),
+                id: StatId::RotationSpeed,
                 value: BASIC[level].rotation_speed,
-                unit: String::from("deg/s"),
+                unit: StatUnit::DegreesPerSecond,
             },
             Stat {
-                key: String::from("Projectile Speed"),
+                id: StatId::ProjectileSpeed,
                 value: BASIC[level].projectile_speed,
-                unit: String::from("tiles/s"),
+                unit: StatUnit::TilesPerSecond,
             },
             Stat {
-                key: String::from("Cost"),
+                id: StatId::Cost,
                 value: get_cost(&BASIC, level),
-                unit: String::from("gold"),
+                unit: StatUnit::Gold,
             },
         ]
     }
 }
 
 impl HasStats for DynamicMultiData {
-    fn stats(&self, level: u32) -> Vec<Stat> {
+    fn stats(level: u32) -> Vec<Stat> {
         let level = level as usize;
         if level >= MULTI.len() {
             return vec![];
@@ -116,46 +123,155 @@ impl HasStats for DynamicMultiData {
 
         vec![
             Stat {
-                key: String::from("Level"),
+                id: StatId::Level,
                 value: (level + 1) as f32,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Range"),
+                id: StatId::Range,
                 value: MULTI[level].range,
-                unit: String::from("tiles"),
+                unit: StatUnit::Tiles,
             },
             Stat {
-                key: String::from("Damage"),
+                id: StatId::Damage,
                 value: MULTI[level].damage,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Attack speed"),
+                id: StatId::AttackSpeed,
                 value: MULTI[level].attack_speed,
-                unit: String::from("/s"),
+                unit: StatUnit::PerSecond,
             },
             Stat {
-                key: String::from("Rotation speed"),
+                id: StatId::RotationSpeed,
                 value: MULTI[level].rotation_speed,
-                unit: String::from("deg/s"),
+                unit: StatUnit::DegreesPerSecond,
             },
             Stat {
-                key: String::from("Projectile Speed"),
+                id: StatId::ProjectileSpeed,
                 value: MULTI[level].projectile_speed,
-                unit: String::from("tiles/s"),
+                unit: StatUnit::TilesPerSecond,
             },
             Stat {
-                key: String::from("Cost"),
+                id: StatId::Cost,
                 value: get_cost(&MULTI, level),
-                unit: String::from("gold"),
+                unit: StatUnit::Gold,
+            },
+        ]
+    }
+}
+
+impl HasStats for DynamicPoisonData {
+    fn stats(level: u32) -> Vec<Stat> {
+        let level = level as usize;
+        if level >= POISON.len() {
+            return vec![];
+        }
+
+        vec![
+            Stat {
+                id: StatId::Level,
+                value: (level + 1) as f32,
+                unit: StatUnit::None,
+            },
+            Stat {
+                id: StatId::Range,
+                value: POISON[level].range,
+                unit: StatUnit::Tiles,
+            },
+            Stat {
+                id: StatId::Damage,
+                value: POISON[level].damage,
+                unit: StatUnit::None,
+            },
+            Stat {
+                id: StatId::PoisonDamage,
+                value: POISON[level].poison_percent,
+                unit: StatUnit::PercentPerSecond,
+            },
+            Stat {
+                id: StatId::PoisonDuration,
+                value: POISON[level].poison_duration,
+                unit: StatUnit::Seconds,
+            },
+            Stat {
+                id: StatId::PoisonStacks,
+                value: POISON[level].max_stacks as f32,
+                unit: StatUnit::None,
+            },
+            Stat {
+                id: StatId::AttackSpeed,
+                value: POISON[level].attack_speed,
+                unit: StatUnit::PerSecond,
+            },
+            Stat {
+                id: StatId::RotationSpeed,
+                value: POISON[level].rotation_speed,
+                unit: StatUnit::DegreesPerSecond,
+            },
+            Stat {
+                id: StatId::ProjectileSpeed,
+                value: POISON[level].projectile_speed,
+                unit: StatUnit::TilesPerSecond,
+            },
+            Stat {
+                id: StatId::Cost,
+                value: get_cost(&POISON, level),
+                unit: StatUnit::Gold,
+            },
+        ]
+    }
+}
+
+impl HasStats for DynamicLaserData {
+    fn stats(level: u32) -> Vec<Stat> {
+        let level = level as usize;
+        if level >= LASER.len() {
+            return vec![];
+        }
+
+        vec![
+            Stat {
+                id: StatId::Level,
+                value: (level + 1) as f32,
+                unit: StatUnit::None,
+            },
+            Stat {
+                id: StatId::Range,
+                value: LASER[level].range,
+                unit: StatUnit::Tiles,
+            },
+            Stat {
+                id: StatId::Damage,
+                value: LASER[level].damage,
+                unit: StatUnit::PerSecond,
+            },
+            Stat {
+                id: StatId::RampMultiplier,
+                value: LASER[level].max_multiplier,
+                unit: StatUnit::None,
+            },
+            Stat {
+                id: StatId::RampTime,
+                value: LASER[level].ramp_time,
+                unit: StatUnit::Seconds,
+            },
+            Stat {
+                id: StatId::RotationSpeed,
+                value: LASER[level].rotation_speed,
+                unit: StatUnit::DegreesPerSecond,
+            },
+            Stat {
+                id: StatId::Cost,
+                value: get_cost(&LASER, level),
+                unit: StatUnit::Gold,
             },
         ]
     }
 }
 
 impl HasStats for DynamicSniperData {
-    fn stats(&self, level: u32) -> Vec<Stat> {
+    fn stats(level: u32) -> Vec<Stat> {
         let level = level as usize;
         if level >= SNIPER.len() {
             return vec![];
@@ -163,46 +279,46 @@ impl HasStats for DynamicSniperData {
 
         vec![
             Stat {
-                key: String::from("Level"),
+                id: StatId::Level,
                 value: (level + 1) as f32,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Range"),
+                id: StatId::Range,
                 value: SNIPER[level].range,
-                unit: String::from("tiles"),
+                unit: StatUnit::Tiles,
             },
             Stat {
-                key: String::from("Damage"),
+                id: StatId::Damage,
                 value: SNIPER[level].damage,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Attack Speed"),
+                id: StatId::AttackSpeed,
                 value: SNIPER[level].attack_speed,
-                unit: String::from("/s"),
+                unit: StatUnit::PerSecond,
             },
             Stat {
-                key: String::from("Rotation speed"),
+                id: StatId::RotationSpeed,
                 value: SNIPER[level].rotation_speed,
-                unit: String::from("deg/s"),
+                unit: StatUnit::DegreesPerSecond,
             },
             Stat {
-                key: String::from("Aiming speed"),
+                id: StatId::AimingSpeed,
                 value: SNIPER[level].aiming_speed,
-                unit: String::from("%/s"),
+                unit: StatUnit::PercentPerSecond,
             },
             Stat {
-                key: String::from("Cost"),
+                id: StatId::Cost,
                 value: get_cost(&SNIPER, level),
-                unit: String::from("gold"),
+                unit: StatUnit::Gold,
             },
         ]
     }
 }
 
 impl HasStats for DynamicCannonData {
-    fn stats(&self, level: u32) -> Vec<Stat> {
+    fn stats(level: u32) -> Vec<Stat> {
         let level = level as usize;
         if level >= CANNON.len() {
             return vec![];
@@ -210,41 +326,41 @@ impl HasStats for DynamicCannonData {
 
         vec![
             Stat {
-                key: String::from("Level"),
+                id: StatId::Level,
                 value: (level + 1) as f32,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Range"),
+                id: StatId::Range,
                 value: CANNON[level].range,
-                unit: String::from("tiles"),
+                unit: StatUnit::Tiles,
             },
             Stat {
-                key: String::from("Damage"),
+                id: StatId::Damage,
                 value: CANNON[level].damage,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Attack Speed"),
+                id: StatId::AttackSpeed,
                 value: CANNON[level].attack_speed,
-                unit: String::from("/s"),
+                unit: StatUnit::PerSecond,
             },
             Stat {
-                key: String::from("Rotation speed"),
+                id: StatId::RotationSpeed,
                 value: CANNON[level].rotation_speed,
-                unit: String::from("deg/s"),
+                unit: StatUnit::DegreesPerSecond,
             },
             Stat {
-                key: String::from("Cost"),
+                id: StatId::Cost,
                 value: get_cost(&CANNON, level),
-                unit: String::from("gold"),
+                unit: StatUnit::Gold,
             },
         ]
     }
 }
 
 impl HasStats for StaticFreezeData {
-    fn stats(&self, level: u32) -> Vec<Stat> {
+    fn stats(level: u32) -> Vec<Stat> {
         let level = level as usize;
         if level >= FREEZE.len() {
             return vec![];
@@ -252,60 +368,160 @@ impl HasStats for StaticFreezeData {
 
         vec![
             Stat {
-                key: String::from("Level"),
+                id: StatId::Level,
                 value: (level + 1) as f32,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Range"),
+                id: StatId::Range,
                 value: FREEZE[level].range,
-                unit: String::from("tiles"),
+                unit: StatUnit::Tiles,
             },
             Stat {
-                key: String::from("Freeze Percent"),
+                id: StatId::FreezePercent,
                 value: FREEZE[level].freeze_percent,
-                unit: String::from(""),
+                unit: StatUnit::None,
             },
             Stat {
-                key: String::from("Freeze  Speed"),
+                id: StatId::FreezeSpeed,
                 value: FREEZE[level].freeze_speed,
-                unit: String::from("/s"),
+                unit: StatUnit::PerSecond,
             },
             Stat {
-                key: String::from("Cost"),
+                id: StatId::Cost,
                 value: get_cost(&FREEZE, level),
-                unit: String::from("gold"),
+                unit: StatUnit::Gold,
             },
         ]
     }
 }
 
 pub fn to_external_turret_with_stats(turret: &Turret, state: &State) -> ExternalTurretWithStats {
+    let external_turret = to_external_turret(turret, state);
     ExternalTurretWithStats {
-        turret: to_external_turret(turret, state),
-        stats: match turret.specific_data {
-            SpecificData::Basic(d) => d.stats(turret.general_data.level),
-            SpecificData::Sniper(d) => d.stats(turret.general_data.level),
-            SpecificData::Cannon(d) => d.stats(turret.general_data.level),
-            SpecificData::Multi(d) => d.stats(turret.general_data.level),
-            SpecificData::Freeze(d) => d.stats(turret.general_data.level),
+        turret: external_turret,
+        stats: tower_stats(external_turret.kind, turret.general_data.level),
+        next_stats: tower_stats(external_turret.kind, turret.general_data.level + 1),
+    }
+}
+
+// empty if the level does not exist
+fn tower_stats(kind: i32, level: u32) -> Vec<Stat> {
+    match kind {
+        0 => DynamicBasicData::stats(level),
+        1 => DynamicSniperData::stats(level),
+        2 => DynamicCannonData::stats(level),
+        3 => DynamicMultiData::stats(level),
+        4 => StaticFreezeData::stats(level),
+        5 => DynamicPoisonData::stats(level),
+        6 => DynamicLaserData::stats(level),
+        _ => vec![],
+    }
+}
+
+fn tower_levels<T: HasCost>(arr: &[T], kind: i32) -> Vec<TowerLevel> {
+    let mut cumulative_cost = 0;
+    arr.iter()
+        .enumerate()
+        .map(|(level, data)| {
+            cumulative_cost += data.get_cost();
+            TowerLevel {
+                level: level as u32,
+                stats: tower_stats(kind, level as u32),
+                cost: data.get_cost(),
+                cumulative_cost,
+            }
+        })
+        .collect()
+}
+
+pub fn get_tower_catalog() -> Vec<TowerCatalogEntry> {
+    vec![
+        TowerCatalogEntry {
+            kind: 0,
+            name: String::from("Basic"),
+            base_cost: BASIC[0].cost,
+            levels: tower_levels(&BASIC, 0),
         },
-        next_stats: match turret.specific_data {
-            SpecificData::Basic(d) => d.stats(turret.general_data.level + 1),
-            SpecificData::Sniper(d) => d.stats(turret.general_data.level + 1),
-            SpecificData::Cannon(d) => d.stats(turret.general_data.level + 1),
-            SpecificData::Multi(d) => d.stats(turret.general_data.level + 1),
-            SpecificData::Freeze(d) => d.stats(turret.general_data.level + 1),
+        TowerCatalogEntry {
+            kind: 1,
+            name: String::from("Sniper"),
+            base_cost: SNIPER[0].cost,
+            levels: tower_levels(&SNIPER, 1),
         },
-    }
+        TowerCatalogEntry {
+            kind: 2,
+            name: String::from("Cannon"),
+            base_cost: CANNON[0].cost,
+            levels: tower_levels(&CANNON, 2),
+        },
+        TowerCatalogEntry {
+            kind: 3,
+            name: String::from("Multi"),
+            base_cost: MULTI[0].cost,
+            levels: tower_levels(&MULTI, 3),
+        },
+        TowerCatalogEntry {
+            kind: 4,
+            name: String::from("Freeze"),
+            base_cost: FREEZE[0].cost,
+            levels: tower_levels(&FREEZE, 4),
+        },
+        TowerCatalogEntry {
+            kind: 5,
+            name: String::from("Poison"),
+            base_cost: POISON[0].cost,
+            levels: tower_levels(&POISON, 5),
+        },
+        TowerCatalogEntry {
+            kind: 6,
+            name: String::from("Laser"),
+            base_cost: LASER[0].cost,
+            levels: tower_levels(&LASER, 6),
+        },
+    ]
 }
 
-#[wasm_bindgen(getter_with_clone)]
-#[derive(Clone)]
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StatId {
+    Level,
+    Range,
+    Damage,
+    AttackSpeed,
+    RotationSpeed,
+    ProjectileSpeed,
+    AimingSpeed,
+    FreezePercent,
+    FreezeSpeed,
+    PoisonDamage,
+    PoisonDuration,
+    PoisonStacks,
+    RampMultiplier,
+    RampTime,
+    Cost,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StatUnit {
+    None,
+    Tiles,
+    PerSecond,
+    PercentPerSecond,
+    DegreesPerSecond,
+    TilesPerSecond,
+    Seconds,
+    Gold,
+}
+
+// use locale::stat_label and locale::unit_label for display strings
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
 pub struct Stat {
-    pub key: String,
+    pub id: StatId,
     pub value: f32,
-    pub unit: String,
+    pub unit: StatUnit,
 }
 
 #[wasm_bindgen(getter_with_clone)]
@@ -316,6 +532,24 @@ pub struct ExternalTurretWithStats {
     pub next_stats: Vec<Stat>,
 }
 
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct TowerLevel {
+    pub level: u32,
+    pub stats: Vec<Stat>,
+    pub cost: u32,            // cost of building (level 0) or upgrading to this level
+    pub cumulative_cost: u32, // total cost of a tower of this level
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct TowerCatalogEntry {
+    pub kind: i32, // see build_tower
+    pub name: String,
+    pub base_cost: u32,
+    pub levels: Vec<TowerLevel>,
+}
+
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone)]
 pub struct TurretRef {
@@ -323,6 +557,127 @@ pub struct TurretRef {
     pub turret_ref: RecycledListRef,
 }
 
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct ExternalCreep {
+    pub creep_ref: RecycledListRef,
+    pub pos: FloatPosition,
+    pub health: f32,
+    pub max_health: f32,
+    pub speed: f32,        // tiles/s, including freeze
+    pub slow_percent: f32, // 100% = standing still
+    pub shield: f32,       // absorbs damage before the health
+    pub max_shield: f32,   // 0 for creeps without shield
+    pub visible: bool,     // false for undetected stealth creeps
+    pub burrowed: bool,    // can't be targeted or hit
+    pub bounty: u32,
+    pub kind: CreepKind,
+    pub progress: f32,         // 0.0 at the spawn, 1.0 at the last goal
+    pub distance_to_goal: f32, // tiles along the creep path
+}
+
+pub fn to_external_creep(
+    creep: &Creep,
+    creep_ref: RecycledListRef,
+    state: &State,
+) -> ExternalCreep {
+    let path_length = (state.creep_path.len() - 1) as f32;
+    let walked = creep.walking.current_goal as f32 + creep.walking.progress_made;
+
+    // the flying path has steps of different lengths
+    let (progress, distance_to_goal) = if creep.is_flying() {
+        let path = state.path_for(creep);
+        let length = |path: &[FloatPosition]| -> f32 {
+            path.windows(2).map(|step| distance(step[0], step[1])).sum()
+        };
+        let next = creep.walking.current_goal as usize + 1;
+        let remaining = distance(creep.pos, path[next]) + length(&path[next..]);
+        (
+            1.0 - remaining / length(path),
+            remaining / state.cell_length,
+        )
+    } else {
+        (walked / path_length, path_length - walked)
+    };
+
+    ExternalCreep {
+        creep_ref,
+        pos: creep.pos,
+        health: creep.health,
+        max_health: creep.max_health,
+        speed: creep.current_speed() * 60.0,
+        slow_percent: creep.slow_speed_accumulated / creep.speed * 100.0,
+        shield: creep.shield.map_or(0.0, |shield| shield.value),
+        max_shield: creep.shield.map_or(0.0, |shield| shield.max_value),
+        visible: creep.visible,
+        burrowed: creep.is_burrowed(),
+        bounty: creep.gold,
+        kind: creep.kind,
+        progress,
+        distance_to_goal,
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct SpawnPreview {
+    pub kind: CreepKind,
+    pub quantity: u32,
+    pub health: f32,         // per creep
+    pub speed: f32,          // tiles/s
+    pub bounty: u32,         // per creep
+    pub leak_damage: f32,    // per creep, in lives
+    pub spawn_interval: u32, // ticks
+    pub delay: u32,          // ticks
+    pub pattern: SpawnPatternKind,
+    pub parallel: bool,
+    pub waypoint: u32, // 0 = creep spawn, n = n-th creep goal
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum SpawnPatternKind {
+    Fixed,
+    Burst,
+    Jitter,
+    Ramp,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WavePreview {
+    pub level: u32,
+    pub groups: Vec<SpawnPreview>,
+}
+
+fn to_spawn_preview(spawn: &Spawn) -> SpawnPreview {
+    SpawnPreview {
+        kind: spawn.kind,
+        quantity: spawn.quantity,
+        health: spawn.health,
+        speed: spawn.speed * 60.0,
+        bounty: spawn.bounty,
+        leak_damage: spawn.leak_damage,
+        spawn_interval: spawn.distance_in_ticks,
+        delay: spawn.delay_in_ticks,
+        pattern: match spawn.pattern {
+            SpawnPattern::Fixed => SpawnPatternKind::Fixed,
+            SpawnPattern::Burst { .. } => SpawnPatternKind::Burst,
+            SpawnPattern::Jitter { .. } => SpawnPatternKind::Jitter,
+            SpawnPattern::Ramp { .. } => SpawnPatternKind::Ramp,
+        },
+        parallel: spawn.parallel,
+        waypoint: spawn.waypoint,
+    }
+}
+
+pub fn to_wave_preview(level: u32, wave: &Wave) -> WavePreview {
+    WavePreview {
+        level,
+        groups: wave.spawns.iter().map(to_spawn_preview).collect(),
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Copy)]
 pub enum GameResult {
@@ -338,6 +693,51 @@ pub struct ExternalState {
     pub health: u32,
     pub game_result: GameResult,
     pub current_level: u32,
+    pub endless: bool,
+    pub highest_wave: u32,
+    pub wave_leak_damage: f32, // lives lost to leaks in the current wave, fractions included
+    pub spawned_creeps: u32,   // added in the current wave, including minions and splitlings
     pub gold: u32,
     pub phase: GamePhase,
 }
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BuildError {
+    None,
+    FightingPhase,
+    UnknownKind,
+    NotEnoughGold,
+    OutOfBounds,
+    Occupied,
+    BlocksPath,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct BuildPreview {
+    pub valid: bool,
+    pub reason: BuildError,
+    pub cost: u32,
+    pub creep_path: Vec<FloatPosition>, // the current path if the build is not valid
+    pub path_length_delta: i32,         // in cells
+    pub range: f32,
+}
+
+#[test]
+fn test_tower_catalog() {
+    let catalog = get_tower_catalog();
+    assert_eq!(catalog.len(), 7);
+
+    let basic = &catalog[0];
+    assert_eq!(basic.base_cost, BASIC[0].cost);
+    assert_eq!(basic.levels.len(), BASIC.len());
+    assert_eq!(basic.levels[1].cost, BASIC[1].cost);
+    assert_eq!(
+        basic.levels[10].cumulative_cost,
+        BASIC.iter().map(|data| data.cost).sum::<u32>()
+    );
+    assert!(catalog
+        .iter()
+        .all(|entry| entry.levels.iter().all(|level| !level.stats.is_empty())));
+}
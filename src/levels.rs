@@ -1,55 +1,316 @@
-use crate::{spawn::Spawn, CreepKind};
+use crate::{
+    spawn::{Spawn, SpawnPattern, Wave},
+    utils::Random,
+    CreepKind,
+};
 
-fn create_normal(level: u32) -> Spawn {
-    Spawn {
-        quantity: 10 + level,
-        distance_in_ticks: 60,
-        health: 34.0 * 1.2_f32.powi(level as i32),
-        speed: 1.0 / 60.0,
-        bounty: 4 + level,
+pub const DEFAULT_WAVE_SCRIPT: &str = include_str!("levels.txt");
+
+// every n-th wave ends with the groups of a boss wave
+pub const BOSS_WAVE_INTERVAL: u32 = 10;
+
+// one group of a wave, see levels.txt for the meaning of the fields
+#[derive(Clone)]
+pub struct GroupScript {
+    pub kind: CreepKind,
+    pub count: f32,
+    pub count_per_level: f32,
+    pub interval: u32,
+    pub delay: u32,
+    pub pattern: SpawnPattern,
+    pub parallel: bool,
+    pub health: f32,
+    pub health_growth: f32,
+    pub speed: f32,
+    pub bounty: f32,
+    pub bounty_per_level: f32,
+    pub spawn: u32,
+    pub leak_damage: f32,
+}
+
+#[derive(Clone)]
+pub struct WaveScript {
+    pub groups: Vec<GroupScript>,
+    pub boss: bool,
+}
+
+fn parse_kind(value: &str) -> Option<CreepKind> {
+    match value {
+        "Normal" => Some(CreepKind::Normal),
+        "Grouped" => Some(CreepKind::Grouped),
+        "Speed" => Some(CreepKind::Speed),
+        "Big" => Some(CreepKind::Big),
+        "Boss" => Some(CreepKind::Boss),
+        "Minion" => Some(CreepKind::Minion),
+        "Healer" => Some(CreepKind::Healer),
+        "Splitter" => Some(CreepKind::Splitter),
+        "Splitling" => Some(CreepKind::Splitling),
+        "Shielded" => Some(CreepKind::Shielded),
+        "Stealth" => Some(CreepKind::Stealth),
+        "Flying" => Some(CreepKind::Flying),
+        "Emp" => Some(CreepKind::Emp),
+        "Enraging" => Some(CreepKind::Enraging),
+        "Carrier" => Some(CreepKind::Carrier),
+        "Burrowing" => Some(CreepKind::Burrowing),
+        "Teleporting" => Some(CreepKind::Teleporting),
+        _ => None,
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for '{key}'"))
+}
+
+fn parse_group(args: &[&str]) -> Result<GroupScript, String> {
+    let mut kind = None;
+    let mut count = None;
+    let mut health = None;
+    let mut bounty = None;
+    let mut pattern = "fixed";
+    let mut burst_size = 5;
+    let mut burst_pause = 120;
+    let mut jitter = 30;
+    let mut seed = 1;
+    let mut ramp_to = None;
+    let mut leak_damage = None;
+    let mut group = GroupScript {
         kind: CreepKind::Normal,
+        count: 0.0,
+        count_per_level: 0.0,
+        interval: 60,
+        delay: 0,
+        pattern: SpawnPattern::Fixed,
+        parallel: false,
+        health: 0.0,
+        health_growth: 1.0,
+        speed: 1.0,
+        bounty: 0.0,
+        bounty_per_level: 0.0,
+        spawn: 0,
+        leak_damage: 0.0,
+    };
+
+    for arg in args {
+        let (key, value) = arg
+            .split_once('=')
+            .ok_or(format!("expected key=value, got '{arg}'"))?;
+        match key {
+            "kind" => {
+                kind = Some(parse_kind(value).ok_or(format!("unknown creep kind '{value}'"))?)
+            }
+            "count" => count = Some(parse_number(key, value)?),
+            "count_per_level" => group.count_per_level = parse_number(key, value)?,
+            "interval" => group.interval = parse_number(key, value)?,
+            "delay" => group.delay = parse_number(key, value)?,
+            "parallel" => group.parallel = parse_number(key, value)?,
+            "pattern" => pattern = value,
+            "burst_size" => burst_size = parse_number(key, value)?,
+            "burst_pause" => burst_pause = parse_number(key, value)?,
+            "jitter" => jitter = parse_number(key, value)?,
+            "seed" => seed = parse_number(key, value)?,
+            "ramp_to" => ramp_to = Some(parse_number(key, value)?),
+            "health" => health = Some(parse_number(key, value)?),
+            "health_growth" => group.health_growth = parse_number(key, value)?,
+            "speed" => group.speed = parse_number(key, value)?,
+            "bounty" => bounty = Some(parse_number(key, value)?),
+            "bounty_per_level" => group.bounty_per_level = parse_number(key, value)?,
+            "spawn" => group.spawn = parse_number(key, value)?,
+            "leak_damage" => leak_damage = Some(parse_number(key, value)?),
+            _ => return Err(format!("unknown key '{key}'")),
+        }
     }
+
+    group.kind = kind.ok_or("missing 'kind'")?;
+    group.count = count.ok_or("missing 'count'")?;
+    group.health = health.ok_or("missing 'health'")?;
+    group.bounty = bounty.ok_or("missing 'bounty'")?;
+    group.leak_damage = leak_damage.unwrap_or(group.kind.default_leak_damage());
+    group.pattern = match pattern {
+        "fixed" => SpawnPattern::Fixed,
+        "burst" if burst_size > 0 => SpawnPattern::Burst {
+            size: burst_size,
+            pause_in_ticks: burst_pause,
+        },
+        "burst" => return Err(String::from("'burst_size' must be at least 1")),
+        "jitter" => SpawnPattern::Jitter {
+            max_jitter_in_ticks: jitter,
+            seed,
+        },
+        "ramp" => SpawnPattern::Ramp {
+            final_distance_in_ticks: ramp_to.ok_or("missing 'ramp_to'")?,
+        },
+        _ => return Err(format!("unknown pattern '{pattern}'")),
+    };
+    Ok(group)
 }
 
-fn create_grouped(level: u32) -> Spawn {
-    Spawn {
-        quantity: (10 + level) * 3,
-        distance_in_ticks: 20,
-        health: (34.0 * 1.2_f32.powi(level as i32)) / 2.0,
-        speed: 1.0 / 60.0,
-        bounty: 2 + level / 2,
-        kind: CreepKind::Grouped,
+pub fn parse_wave_script(script: &str) -> Result<Vec<WaveScript>, String> {
+    let mut waves: Vec<WaveScript> = vec![];
+
+    for (i, line) in script.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.first() {
+            None => continue,
+            Some(&"wave") if tokens.len() == 1 => waves.push(WaveScript {
+                groups: vec![],
+                boss: false,
+            }),
+            Some(&"boss") if tokens.len() == 1 => waves.push(WaveScript {
+                groups: vec![],
+                boss: true,
+            }),
+            Some(&"group") => {
+                let group =
+                    parse_group(&tokens[1..]).map_err(|e| format!("line {}: {e}", i + 1))?;
+                match waves.last_mut() {
+                    Some(wave) => wave.groups.push(group),
+                    None => return Err(format!("line {}: group outside of a wave", i + 1)),
+                }
+            }
+            Some(_) => return Err(format!("line {}: unexpected '{line}'", i + 1)),
+        }
+    }
+
+    if waves.iter().all(|wave| wave.boss) {
+        return Err(String::from("the script contains no waves"));
     }
+    if let Some(i) = waves.iter().position(|wave| wave.groups.is_empty()) {
+        return Err(format!("wave {} has no groups", i + 1));
+    }
+
+    Ok(waves)
 }
 
-fn create_speed(level: u32) -> Spawn {
+fn create_spawn(group: &GroupScript, level: u32) -> Spawn {
     Spawn {
-        quantity: 10 + level,
-        distance_in_ticks: 60,
-        health: 34.0 * 1.2_f32.powi(level as i32),
-        speed: 1.4 / 60.0,
-        bounty: 4 + level,
-        kind: CreepKind::Speed,
+        quantity: (group.count + group.count_per_level * level as f32) as u32,
+        distance_in_ticks: group.interval,
+        delay_in_ticks: group.delay,
+        pattern: group.pattern,
+        parallel: group.parallel,
+        health: group.health * group.health_growth.powi(level as i32),
+        speed: group.speed / 60.0,
+        bounty: (group.bounty + group.bounty_per_level * level as f32) as u32,
+        kind: group.kind,
+        waypoint: group.spawn,
+        leak_damage: group.leak_damage,
     }
 }
 
-fn create_big(level: u32) -> Spawn {
-    Spawn {
-        quantity: 5 + level / 2,
-        distance_in_ticks: 120,
-        health: (34.0 * 1.2_f32.powi(level as i32)) * 2.5,
-        speed: 0.9 / 60.0,
-        bounty: (4 + level) * 2,
-        kind: CreepKind::Big,
+fn regular_waves(waves: &[WaveScript]) -> Vec<&WaveScript> {
+    waves.iter().filter(|wave| !wave.boss).collect()
+}
+
+pub fn create_level(waves: &[WaveScript], current_level: u32) -> Wave {
+    let regular_waves = regular_waves(waves);
+    let wave = regular_waves[current_level as usize % regular_waves.len()];
+    let mut spawns: Vec<Spawn> = wave
+        .groups
+        .iter()
+        .map(|group| create_spawn(group, current_level))
+        .collect();
+
+    let boss_waves: Vec<&WaveScript> = waves.iter().filter(|wave| wave.boss).collect();
+    let milestone = (current_level + 1) / BOSS_WAVE_INTERVAL;
+    if !boss_waves.is_empty() && (current_level + 1).is_multiple_of(BOSS_WAVE_INTERVAL) {
+        let boss_wave = boss_waves[(milestone - 1) as usize % boss_waves.len()];
+        spawns.extend(
+            boss_wave
+                .groups
+                .iter()
+                .map(|group| create_spawn(group, current_level)),
+        );
     }
+
+    Wave { spawns }
 }
 
-pub fn create_level(current_level: u32) -> Spawn {
-    match current_level % 4 {
-        0 => create_normal(current_level),
-        1 => create_grouped(current_level),
-        2 => create_speed(current_level),
-        3 => create_big(current_level),
-        _ => todo!(),
+// Waves after `max_level` (endless mode) add the groups of randomly chosen scripted
+// waves to the regular one, one more wave every 10 levels.
+pub fn create_endless_level(waves: &[WaveScript], current_level: u32, max_level: u32) -> Wave {
+    let mut wave = create_level(waves, current_level);
+    let mut random = Random::new(current_level);
+    let waves = regular_waves(waves);
+
+    let extra_waves = 1 + current_level.saturating_sub(max_level) / 10;
+    for _ in 0..extra_waves {
+        let other = waves[random.next_up_to(waves.len() as u32 - 1) as usize];
+        for group in other.groups.iter() {
+            let mut spawn = create_spawn(group, current_level);
+            spawn.quantity = spawn.quantity.div_ceil(2);
+            spawn.parallel = true;
+            wave.spawns.push(spawn);
+        }
     }
+
+    wave
+}
+
+#[test]
+fn test_parse_wave_script() {
+    let waves = parse_wave_script(DEFAULT_WAVE_SCRIPT).unwrap();
+    assert_eq!(waves.len(), 5);
+
+    let big = &create_level(&waves, 7).spawns[0];
+    assert!(matches!(big.kind, CreepKind::Big));
+    assert_eq!(big.quantity, 8);
+    assert_eq!(big.bounty, 22);
+
+    let waves = parse_wave_script(
+        "wave\n\
+         group kind=Normal count=10 health=30 bounty=3\n\
+         group kind=Big count=3 health=80 bounty=8 delay=120 # mixed with\n\
+         group kind=Speed count=3 health=30 bounty=4 interval=20 spawn=1 parallel=true\n\
+         group kind=Grouped count=20 health=15 bounty=2 pattern=burst burst_size=4\n",
+    )
+    .unwrap();
+    assert_eq!(waves[0].groups.len(), 4);
+    assert_eq!(waves[0].groups[2].spawn, 1);
+    assert!(waves[0].groups[2].parallel);
+    assert!(matches!(
+        waves[0].groups[3].pattern,
+        SpawnPattern::Burst { size: 4, .. }
+    ));
+
+    assert!(parse_wave_script("group kind=Normal count=1 health=1 bounty=1").is_err());
+    assert!(parse_wave_script("wave\ngroup kind=Dragon count=1 health=1 bounty=1").is_err());
+    assert!(parse_wave_script("wave\ngroup kind=Normal health=1 bounty=1").is_err());
+    assert!(parse_wave_script("wave\n").is_err());
+    assert!(
+        parse_wave_script("wave\ngroup kind=Normal count=1 health=1 bounty=1 pattern=ramp")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_create_endless_level() {
+    let waves = parse_wave_script(DEFAULT_WAVE_SCRIPT).unwrap();
+
+    let scripted = create_level(&waves, 50);
+    let endless = create_endless_level(&waves, 50, 50);
+    assert_eq!(endless.spawns.len(), scripted.spawns.len() + 1);
+    assert!(endless.spawns[1].parallel);
+
+    // three extra waves with at least one group each
+    assert!(
+        create_endless_level(&waves, 75, 50).spawns.len()
+            >= create_level(&waves, 75).spawns.len() + 3
+    );
+}
+
+#[test]
+fn test_boss_waves() {
+    let waves = parse_wave_script(DEFAULT_WAVE_SCRIPT).unwrap();
+
+    assert_eq!(create_level(&waves, 5).spawns.len(), 1);
+    let milestone = create_level(&waves, 9);
+    assert_eq!(milestone.spawns.len(), 2);
+    assert!(matches!(milestone.spawns[1].kind, CreepKind::Boss));
+    assert!(!milestone.spawns[1].parallel);
+    assert!(milestone.spawns[1].health > milestone.spawns[0].health * 5.0);
+
+    assert!(parse_wave_script("boss\ngroup kind=Boss count=1 health=1 bounty=1").is_err());
 }
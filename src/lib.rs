@@ -1,21 +1,36 @@
 mod art;
+mod ascii;
+mod boss;
 mod entities;
+mod events;
 mod external;
 mod levels;
+mod locale;
 mod path;
 mod recycled_list;
+mod settings;
 mod spawn;
+mod svg;
 mod utils;
 
-use art::Art;
+use art::{Art, Renderer};
+use boss::create_minions;
 use entities::*;
+use events::{EventQueue, GameEvent, GameEventKind};
 use external::{
-    to_external_turret, to_external_turret_with_stats, ExternalState, GameResult, TurretRef,
+    get_tower_catalog, to_external_creep, to_external_turret, to_external_turret_with_stats,
+    to_wave_preview, BuildError, BuildPreview, ExternalCreep, ExternalState, GameResult,
+    TowerCatalogEntry, TurretRef, WavePreview,
+};
+use levels::{
+    create_endless_level, create_level, parse_wave_script, WaveScript, BOSS_WAVE_INTERVAL,
+    DEFAULT_WAVE_SCRIPT,
 };
-use levels::create_level;
 use path::find_path;
 use recycled_list::{RecycledList, RecycledListItem, RecycledListRef};
-use spawn::Spawner;
+use settings::GameSettings;
+use spawn::{Spawner, Wave};
+use svg::SvgRenderer;
 use utils::{
     distance, to_creep_position, to_float_position, to_grid_position, FloatPosition, GridPosition,
 };
@@ -26,20 +41,26 @@ pub struct Game {
     state: State,
     turret_state: RecycledList<Turret>,
     cannon_particles: RecycledList<CannonParticle>,
+    teleport_particles: RecycledList<TeleportParticle>,
     spawner: Spawner,
+    waves: Vec<WaveScript>,
+    settings: GameSettings,
 }
 
-fn compute_creep_paths(game: &Game) -> Option<Vec<FloatPosition>> {
+fn compute_creep_paths(
+    state: &State,
+    turrets: &RecycledList<Turret>,
+) -> Option<Vec<FloatPosition>> {
     let mut paths = vec![];
 
-    let mut start = game.state.creep_spawn;
-    for goal in game.state.creep_goals.iter() {
+    let mut start = state.creep_spawn;
+    for goal in state.creep_goals.iter() {
         match find_path(
             start,
             *goal,
-            game.state.board_dimension_x,
-            game.state.board_dimension_y,
-            &game.turret_state,
+            state.board_dimension_x,
+            state.board_dimension_y,
+            turrets,
         ) {
             Some(path) => paths.push(path),
             None => return None,
@@ -52,7 +73,7 @@ fn compute_creep_paths(game: &Game) -> Option<Vec<FloatPosition>> {
         .map(|path| -> Vec<FloatPosition> {
             path.0
                 .iter()
-                .map(|grid_pos| to_creep_position(*grid_pos, game.state.cell_length))
+                .map(|grid_pos| to_creep_position(*grid_pos, state.cell_length))
                 .collect()
         })
         .collect();
@@ -69,6 +90,10 @@ fn compute_creep_paths(game: &Game) -> Option<Vec<FloatPosition>> {
 #[wasm_bindgen]
 impl Game {
     pub fn new() -> Self {
+        Game::new_with_settings(GameSettings::default())
+    }
+
+    pub fn new_with_settings(settings: GameSettings) -> Self {
         utils::set_panic_hook();
 
         let creep_spawn = GridPosition { x: 2, y: 0 };
@@ -86,27 +111,43 @@ impl Game {
                 GridPosition { x: 20, y: 27 },
             ],
             creep_path: vec![],
+            flying_path: vec![],
             creeps: RecycledList::new(),
             particles: RecycledList::new(),
             sniper_particles: RecycledList::new(),
             multi_particles: RecycledList::new(),
             cell_length,
-            health: 10,
+            health: settings.lives,
             still_running: true,
             current_level: 0,
             max_level: 50,
+            endless: false,
+            highest_wave: 0,
+            leak_damage_total: 0.0,
+            wave_leak_damage: 0.0,
+            wave_spawned_creeps: 0,
+            events: EventQueue::default(),
             game_phase: GamePhase::Building,
-            gold: 200,
+            gold: settings.starting_gold,
             tick: 0,
         };
 
+        let waves = parse_wave_script(DEFAULT_WAVE_SCRIPT).unwrap();
         let mut game = Game {
             state,
             turret_state: RecycledList::new(),
             cannon_particles: RecycledList::new(),
-            spawner: Spawner::new(to_creep_position(creep_spawn, cell_length), create_level(0)),
+            teleport_particles: RecycledList::new(),
+            spawner: Spawner::new(Wave { spawns: vec![] }),
+            waves,
+            settings,
         };
-        game.state.creep_path = compute_creep_paths(&game).unwrap();
+        game.spawner.set_wave(game.create_wave(0));
+        game.state.creep_path = compute_creep_paths(&game.state, &game.turret_state).unwrap();
+        game.state.flying_path = std::iter::once(game.state.creep_spawn)
+            .chain(game.state.creep_goals.iter().copied())
+            .map(|pos| to_creep_position(pos, game.state.cell_length))
+            .collect();
 
         game
     }
@@ -116,7 +157,7 @@ impl Game {
 
         let game_result = if state.still_running {
             GameResult::StillRunning
-        } else if state.current_level >= state.max_level {
+        } else if !state.endless && state.current_level >= state.max_level {
             GameResult::PlayerWon
         } else {
             GameResult::CreepsWon
@@ -127,156 +168,106 @@ impl Game {
             health: state.health,
             game_result,
             current_level: state.current_level,
+            endless: state.endless,
+            highest_wave: state.highest_wave,
+            wave_leak_damage: state.wave_leak_damage,
+            spawned_creeps: state.wave_spawned_creeps,
             gold: state.gold,
             phase: state.game_phase.clone(),
         }
     }
 
-    pub fn draw_state(&self, art: &Art, time: f32) {
-        let state = &self.state;
-
-        art.clear();
-        art.drawMap(
-            state.board_dimension_x as f32 * state.cell_length,
-            state.board_dimension_y as f32 * state.cell_length,
-        );
-
-        art.startCreepPath(state.creep_path[0].x, state.creep_path[0].y, time);
-        for line in &state.creep_path[1..] {
-            art.drawCreepPathLine(line.x, line.y);
-        }
-        art.endCreepPath();
-
-        let creep_spawn = to_float_position(state.creep_spawn, state.cell_length);
-        art.drawCreepSpawn(creep_spawn.x, creep_spawn.y, state.cell_length);
+    pub fn get_settings(&self) -> GameSettings {
+        self.settings
+    }
 
-        for goal in &state.creep_goals {
-            let creep_goal = to_float_position(*goal, state.cell_length);
-            art.drawCreepGoal(creep_goal.x, creep_goal.y, state.cell_length);
-        }
+    pub fn get_wave_preview(&self, level: u32) -> WavePreview {
+        to_wave_preview(level, &self.create_wave(level))
+    }
 
-        for turret in self.turret_state.iter() {
-            let external_turret = to_external_turret(turret, state);
-            art.drawTurret(
-                external_turret.pos.x,
-                external_turret.pos.y,
-                external_turret.rotation,
-                state.cell_length,
-                external_turret.kind,
-            )
-        }
-
-        for particle in self.cannon_particles.iter() {
-            art.drawCannonParticle(
-                particle.pos.x,
-                particle.pos.y,
-                particle.explosion_radius
-                    * self.state.cell_length
-                    * particle.lifetime_in_ticks as f32
-                    / 20.0,
-            );
+    /// The current wave and the following ones, at most `n`.
+    pub fn get_upcoming_waves(&self, n: u32) -> Vec<WavePreview> {
+        let first = self.state.current_level;
+        let mut last = first.saturating_add(n);
+        if !self.state.endless {
+            last = u32::min(last, self.state.max_level);
         }
+        (first..last)
+            .map(|level| self.get_wave_preview(level))
+            .collect()
+    }
 
-        for particle in self.state.sniper_particles.iter() {
-            art.drawSniperParticle(
-                particle.start_pos.x,
-                particle.start_pos.y,
-                particle.target_pos.x,
-                particle.target_pos.y,
-            );
-        }
+    /// Events since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        self.state.events.take()
+    }
 
-        for creep in state.creeps.iter() {
-            art.drawCreep(
-                creep.pos.x,
-                creep.pos.y,
-                creep.health / creep.max_health,
-                creep.kind,
-            );
-        }
+    pub fn draw_state(&self, art: &Art, time: f32) {
+        self.draw(art, time);
+    }
 
-        for particle in state.particles.iter() {
-            art.drawParticle(particle.pos.x, particle.pos.y);
-        }
+    /// Snapshot of the current tick as svg document, e.g. for bug reports.
+    pub fn to_svg(&self) -> String {
+        let svg = SvgRenderer::new();
+        self.draw(&svg, self.state.tick as f32);
+        svg.to_svg()
+    }
 
-        for particle in state.multi_particles.iter() {
-            art.drawParticle(particle.pos.x, particle.pos.y);
-        }
+    /// Text picture of the board plus a status line, see `ascii.rs` for the legend.
+    pub fn to_ascii(&self) -> String {
+        ascii::render(&self.state, &self.turret_state)
     }
 
     // fixme: create enum for kind instead of error-prone i32
     pub fn build_tower(&mut self, x: f32, y: f32, kind: i32) {
-        if let GamePhase::Fighting = self.state.game_phase {
-            return;
-        }
-
-        let cost = match kind {
-            0 => BASIC[0].cost,
-            1 => SNIPER[0].cost,
-            2 => CANNON[0].cost,
-            3 => MULTI[0].cost,
-            4 => FREEZE[0].cost,
-            _ => panic!("gotcha! tower kind not implemented!"),
+        let turret = match self.check_build(x, y, kind) {
+            Ok(turret) => turret,
+            Err(_) => return,
         };
+        let cost = tower_cost(kind).unwrap();
 
-        if self.state.gold < cost {
-            return;
-        }
+        let tower_ref = self.turret_state.add(turret);
 
-        if x < 0.0 || y < 0.0 {
-            return;
-        }
-
-        let grid_pos = to_grid_position(FloatPosition { x, y }, self.state.cell_length);
-        if grid_pos.x >= self.state.board_dimension_x || grid_pos.y >= self.state.board_dimension_y
-        {
-            return;
+        match compute_creep_paths(&self.state, &self.turret_state) {
+            Some(p) => {
+                self.state.creep_path = p;
+                self.state.gold -= cost;
+            }
+            _ => self.turret_state.remove(tower_ref),
         }
+    }
 
-        if self
-            .turret_state
-            .iter()
-            .any(|x| x.general_data.pos == grid_pos)
-        {
-            return;
-        }
+    /// Result of `build_tower` with the same arguments, without changing the game.
+    pub fn preview_build(&self, x: f32, y: f32, kind: i32) -> BuildPreview {
+        let mut preview = BuildPreview {
+            valid: false,
+            reason: BuildError::None,
+            cost: tower_cost(kind).unwrap_or(0),
+            creep_path: self.state.creep_path.clone(),
+            path_length_delta: 0,
+            range: tower_range(kind).unwrap_or(0.0) * self.state.cell_length,
+        };
 
-        let tower_ref = self.turret_state.add(Turret {
-            general_data: GeneralData {
-                pos: grid_pos,
-                last_shot: self.state.tick,
-                level: 0,
-            },
-            specific_data: match kind {
-                0 => SpecificData::Basic(DynamicBasicData {
-                    rotation: 0.0,
-                    target: RecycledListRef::null_ref(),
-                }),
-                1 => SpecificData::Sniper(DynamicSniperData {
-                    rotation: 0.0,
-                    target: RecycledListRef::null_ref(),
-                    aiming_ticks: 0,
-                }),
-                2 => SpecificData::Cannon(DynamicCannonData {
-                    rotation: 0.0,
-                    target: RecycledListRef::null_ref(),
-                }),
-                3 => SpecificData::Multi(DynamicMultiData {
-                    rotation: 0.0,
-                    target: RecycledListRef::null_ref(),
-                }),
-                4 => SpecificData::Freeze(FREEZE[0]),
-                _ => panic!("gotcha! tower kind not implemented!"),
-            },
-        });
+        let turret = match self.check_build(x, y, kind) {
+            Ok(turret) => turret,
+            Err(reason) => {
+                preview.reason = reason;
+                return preview;
+            }
+        };
 
-        match compute_creep_paths(self) {
+        let mut turrets = self.turret_state.clone();
+        turrets.add(turret);
+        match compute_creep_paths(&self.state, &turrets) {
             Some(p) => {
-                self.state.creep_path = p;
-                self.state.gold -= cost;
+                preview.valid = true;
+                preview.path_length_delta = p.len() as i32 - self.state.creep_path.len() as i32;
+                preview.creep_path = p;
             }
-            _ => self.turret_state.remove(tower_ref),
+            None => preview.reason = BuildError::BlocksPath,
         }
+
+        preview
     }
 
     pub fn get_tower_at(&self, x: f32, y: f32) -> Option<TurretRef> {
@@ -291,6 +282,14 @@ impl Game {
         })
     }
 
+    pub fn get_tower_catalog(&self) -> Vec<TowerCatalogEntry> {
+        get_tower_catalog()
+    }
+
+    pub fn get_detector_cost(&self) -> u32 {
+        DETECTOR_COST
+    }
+
     pub fn get_tower_by_ref(&self, turret_ref: RecycledListRef) -> Option<TurretRef> {
         self.turret_state.get(turret_ref).map(|turret| TurretRef {
             data: to_external_turret_with_stats(turret, &self.state),
@@ -298,6 +297,34 @@ impl Game {
         })
     }
 
+    pub fn get_creep_at(&self, x: f32, y: f32) -> Option<ExternalCreep> {
+        let pos = FloatPosition { x, y };
+        self.state
+            .creeps
+            .enumerate()
+            .map(|creep_item| (distance(creep_item.data.pos, pos), creep_item))
+            .filter(|(d, _)| *d <= self.state.cell_length / 2.0)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, creep_item)| {
+                to_external_creep(&creep_item.data, creep_item.item_ref, &self.state)
+            })
+    }
+
+    pub fn get_creep_by_ref(&self, creep_ref: RecycledListRef) -> Option<ExternalCreep> {
+        self.state
+            .creeps
+            .get(creep_ref)
+            .map(|creep| to_external_creep(creep, creep_ref, &self.state))
+    }
+
+    pub fn get_creeps(&self) -> Vec<ExternalCreep> {
+        self.state
+            .creeps
+            .enumerate()
+            .map(|creep_item| to_external_creep(&creep_item.data, creep_item.item_ref, &self.state))
+            .collect()
+    }
+
     pub fn sell_tower(&mut self, id: u32, index: usize) {
         if let GamePhase::Fighting = self.state.game_phase {
             return;
@@ -311,21 +338,48 @@ impl Game {
         }
 
         let tower = tower_option.unwrap();
-        match tower.specific_data {
-            SpecificData::Basic(_) => self.state.gold += BASIC[0].cost,
-            SpecificData::Sniper(_) => self.state.gold += SNIPER[0].cost,
-            SpecificData::Cannon(_) => self.state.gold += CANNON[0].cost,
-            SpecificData::Multi(_) => self.state.gold += MULTI[0].cost,
-            SpecificData::Freeze(_) => self.state.gold += FREEZE[0].cost,
-        }
+        let cost = match tower.specific_data {
+            SpecificData::Basic(_) => BASIC[0].cost,
+            SpecificData::Sniper(_) => SNIPER[0].cost,
+            SpecificData::Cannon(_) => CANNON[0].cost,
+            SpecificData::Multi(_) => MULTI[0].cost,
+            SpecificData::Freeze(_) => FREEZE[0].cost,
+            SpecificData::Poison(_) => POISON[0].cost,
+            SpecificData::Laser(_) => LASER[0].cost,
+        };
+        self.state.gold += self.settings.refund(cost);
         self.turret_state.remove(turret_ref);
 
         // update creep path
-        if let Some(p) = compute_creep_paths(self) {
+        if let Some(p) = compute_creep_paths(&self.state, &self.turret_state) {
             self.state.creep_path = p;
         }
     }
 
+    pub fn set_targeting_mode(&mut self, id: u32, index: usize, targeting: TargetingMode) {
+        if let Some(tower) = self.turret_state.get_mut(RecycledListRef { id, index }) {
+            tower.general_data.targeting = targeting;
+        }
+    }
+
+    /// Lets the tower reveal stealth creeps within its range.
+    pub fn buy_detector(&mut self, id: u32, index: usize) {
+        if let GamePhase::Fighting = self.state.game_phase {
+            return;
+        }
+
+        let tower = match self.turret_state.get_mut(RecycledListRef { id, index }) {
+            Some(tower) => tower,
+            None => return,
+        };
+        if tower.general_data.detector || self.state.gold < DETECTOR_COST {
+            return;
+        }
+
+        self.state.gold -= DETECTOR_COST;
+        tower.general_data.detector = true;
+    }
+
     pub fn upgrade_tower(&mut self, id: u32, index: usize) {
         if let GamePhase::Fighting = self.state.game_phase {
             return;
@@ -344,6 +398,8 @@ impl Game {
             SpecificData::Cannon(_) => CANNON.len(),
             SpecificData::Multi(_) => MULTI.len(),
             SpecificData::Freeze(_) => FREEZE.len(),
+            SpecificData::Poison(_) => POISON.len(),
+            SpecificData::Laser(_) => LASER.len(),
         };
         let next_level = (tower.general_data.level + 1) as usize;
         if next_level >= max_level {
@@ -356,6 +412,8 @@ impl Game {
             SpecificData::Cannon(_) => CANNON[next_level].cost,
             SpecificData::Multi(_) => MULTI[next_level].cost,
             SpecificData::Freeze(_) => FREEZE[next_level].cost,
+            SpecificData::Poison(_) => POISON[next_level].cost,
+            SpecificData::Laser(_) => LASER[next_level].cost,
         };
 
         if self.state.gold < cost {
@@ -366,9 +424,39 @@ impl Game {
         tower.general_data.level += 1;
     }
 
+    /// Replaces the waves (see levels.txt for the format), starting with the current level.
+    pub fn load_wave_script(&mut self, script: &str) -> Result<(), String> {
+        if let GamePhase::Fighting = self.state.game_phase {
+            return Err(String::from("waves can only be changed while building"));
+        }
+
+        self.waves = parse_wave_script(script)?;
+        self.spawner
+            .set_wave(self.create_wave(self.state.current_level));
+        Ok(())
+    }
+
+    /// Keep playing after `max_level` with generated waves, also after the game was won.
+    pub fn set_endless(&mut self, endless: bool) {
+        if let GamePhase::Fighting = self.state.game_phase {
+            return;
+        }
+        if let GameResult::PlayerWon = self.get_state().game_result {
+            self.state.still_running = endless;
+        }
+        self.state.endless = endless;
+    }
+
     pub fn start_wave(&mut self) {
+        if !self.state.still_running {
+            return;
+        }
         if let GamePhase::Building = self.state.game_phase {
             self.state.game_phase = GamePhase::Fighting;
+            self.state.wave_leak_damage = 0.0;
+            self.state.wave_spawned_creeps = 0;
+            self.state.highest_wave =
+                u32::max(self.state.highest_wave, self.state.current_level + 1);
         }
     }
 
@@ -380,10 +468,8 @@ impl Game {
             return;
         }
 
-        let creep_to_spawn = self.spawner.tick();
-
-        if let Some(creep) = creep_to_spawn {
-            self.state.creeps.add(creep);
+        for creep in self.spawner.tick(&self.state) {
+            self.state.add_creep(creep);
         }
 
         // Reset freeze on all creepsa
@@ -392,12 +478,16 @@ impl Game {
             creep.delta_speed = -0.002 / 60.0; //creeps recover a fixed amount of 15 per second
         }
         for turret in self.turret_state.iter() {
+            if turret.general_data.is_disabled(self.state.tick) {
+                continue;
+            }
             if let SpecificData::Freeze(turret_data) = turret.specific_data {
                 for creep in self.state.creeps.iter_mut().filter(|the_creep| {
-                    distance(
-                        the_creep.pos,
-                        turret.general_data.get_float_pos(self.state.cell_length),
-                    ) <= turret_data.range * self.state.cell_length
+                    !the_creep.is_flying()
+                        && distance(
+                            the_creep.pos,
+                            turret.general_data.get_float_pos(self.state.cell_length),
+                        ) <= turret_data.range * self.state.cell_length
                 }) {
                     creep.max_freeze_percent =
                         f32::max(creep.max_freeze_percent, turret_data.freeze_percent);
@@ -431,17 +521,154 @@ impl Game {
             creep.last_freeze_percent = creep.slow_speed_accumulated / creep.speed;
         }
 
+        // Creep abilities
+        let mut minions = vec![];
+        let mut pulses = vec![];
+        let mut poisoned = vec![];
+        for creep_item in self.state.creeps.enumerate_mut() {
+            let creep = &mut creep_item.data;
+            creep.ticks_alive += 1;
+            if let Some(poison) = &mut creep.poison {
+                creep.health -= poison.tick();
+                if poison.remaining_ticks == 0 {
+                    creep.poison = None;
+                }
+                if creep.health <= 0.0 {
+                    poisoned.push(creep_item.item_ref);
+                }
+            }
+            if creep.emits_pulse() {
+                pulses.push(creep.pos);
+            }
+            if creep.teleports() {
+                // never skip the last goal, leaking is up to the movement below
+                let last_goal = self.state.creep_path.len() as u32 - 2;
+                let cells = u32::min(
+                    TELEPORT_CELLS,
+                    last_goal.saturating_sub(creep.walking.current_goal),
+                );
+                if cells > 0 {
+                    let from = creep.pos;
+                    creep.walking.current_goal += cells;
+                    let a = self.state.creep_path[creep.walking.current_goal as usize];
+                    let b = self.state.creep_path[creep.walking.current_goal as usize + 1];
+                    creep.pos =
+                        a * (1.0 - creep.walking.progress_made) + b * creep.walking.progress_made;
+                    for (kind, pos) in [
+                        (GameEventKind::TeleportOut, from),
+                        (GameEventKind::TeleportIn, creep.pos),
+                    ] {
+                        self.state.events.push(GameEvent {
+                            kind,
+                            tick: self.state.tick,
+                            pos,
+                            creep_kind: Some(creep.kind),
+                            value: cells as f32,
+                        });
+                        self.teleport_particles.add(TeleportParticle {
+                            pos,
+                            lifetime_in_ticks: TELEPORT_EFFECT_LIFETIME_IN_TICKS,
+                        });
+                    }
+                }
+            }
+            if let Some(shield) = &mut creep.shield {
+                shield.tick();
+            }
+            if creep.boss.as_mut().is_some_and(|boss| boss.tick()) {
+                minions.extend(create_minions(creep));
+            }
+            if creep.boss.is_some_and(|boss| boss.is_slow_immune()) {
+                creep.slow_speed_accumulated = 0.0;
+                creep.last_freeze_percent = 0.0;
+            }
+        }
+        for creep in poisoned {
+            self.state.kill_creep(creep);
+        }
+        for minion in minions {
+            self.state.add_creep(minion);
+        }
+        for pos in pulses {
+            for turret in self.turret_state.iter_mut().filter(|turret| {
+                distance(
+                    turret.general_data.get_float_pos(self.state.cell_length),
+                    pos,
+                ) <= EMP_RANGE * self.state.cell_length
+            }) {
+                turret.general_data.disabled_until = self.state.tick + EMP_DURATION_IN_TICKS;
+            }
+            self.state.events.push(GameEvent {
+                kind: GameEventKind::EmpPulse,
+                tick: self.state.tick,
+                pos,
+                creep_kind: Some(CreepKind::Emp),
+                value: EMP_RANGE,
+            });
+        }
+
+        // Healers
+        if self.state.tick.is_multiple_of(HEALER_INTERVAL_IN_TICKS) {
+            let healers: Vec<(FloatPosition, RecycledListRef)> = self
+                .state
+                .creeps
+                .enumerate()
+                .filter(|creep_item| matches!(creep_item.data.kind, CreepKind::Healer))
+                .map(|creep_item| (creep_item.data.pos, creep_item.item_ref))
+                .collect();
+            let range = HEALER_RANGE * self.state.cell_length;
+            for creep_item in self.state.creeps.enumerate_mut() {
+                let creep = &mut creep_item.data;
+                let heals = healers
+                    .iter()
+                    .filter(|(pos, healer)| {
+                        *healer != creep_item.item_ref && distance(*pos, creep.pos) <= range
+                    })
+                    .count();
+                creep.health = f32::min(
+                    creep.max_health,
+                    creep.health + creep.max_health * HEALER_HEAL_PERCENT / 100.0 * heals as f32,
+                );
+            }
+        }
+
         let mut creeps_to_remove: Vec<RecycledListRef> = vec![];
         for creep_item in self.state.creeps.enumerate_mut() {
             let creep = &mut creep_item.data;
-            creep.walking.progress_made += creep.speed - creep.slow_speed_accumulated;
+            let path = if creep.is_flying() {
+                &self.state.flying_path
+            } else {
+                &self.state.creep_path
+            };
+            // flying creeps cover long straight lines, the creep path has one cell per step
+            let step_length = if creep.is_flying() {
+                let goal = creep.walking.current_goal as usize;
+                distance(path[goal], path[goal + 1]) / self.state.cell_length
+            } else {
+                1.0
+            };
+            creep.walking.progress_made += creep.current_speed() / step_length;
             if creep.walking.progress_made >= 1.0 {
                 creep.walking.current_goal += 1;
                 creep.walking.progress_made = 0.0;
             }
-            if creep.walking.current_goal == self.state.creep_path.len() as u32 - 1 {
+            if creep.walking.current_goal == path.len() as u32 - 1 {
                 creeps_to_remove.push(creep_item.item_ref);
-                self.state.health -= 1;
+
+                // fractions of lives add up over the game
+                let lives_before = self.state.leak_damage_total as u32;
+                self.state.leak_damage_total += creep.leak_damage;
+                self.state.wave_leak_damage += creep.leak_damage;
+                let lives_lost = self.state.leak_damage_total as u32 - lives_before;
+                self.state.events.push(GameEvent {
+                    kind: GameEventKind::CreepLeaked,
+                    tick: self.state.tick,
+                    pos: creep.pos,
+                    creep_kind: Some(creep.kind),
+                    value: creep.leak_damage,
+                });
+
+                self.state.health = self.state.health.saturating_sub(lives_lost);
                 if self.state.health == 0 {
                     self.state.still_running = false;
                     return;
@@ -450,7 +677,6 @@ impl Game {
             }
             // update creep position
             {
-                let path = &self.state.creep_path;
                 let a = path[creep.walking.current_goal as usize];
                 let b = path[creep.walking.current_goal as usize + 1];
                 let pos = a * (1.0 - creep.walking.progress_made) + b * creep.walking.progress_made;
@@ -464,22 +690,62 @@ impl Game {
         if self.spawner.is_finished() && self.state.creeps.is_empty() {
             self.state.current_level += 1;
 
+            let lives = u32::min(
+                self.settings.milestone_lives,
+                self.settings.lives.saturating_sub(self.state.health),
+            );
+            if self.state.current_level.is_multiple_of(BOSS_WAVE_INTERVAL) && lives > 0 {
+                self.state.health += lives;
+                self.state.events.push(GameEvent {
+                    kind: GameEventKind::LivesRegained,
+                    tick: self.state.tick,
+                    pos: *self.state.creep_path.last().unwrap(),
+                    creep_kind: None,
+                    value: lives as f32,
+                });
+            }
+
             self.state.particles.clear();
             self.cannon_particles.clear();
+            self.teleport_particles.clear();
             self.state.sniper_particles.clear();
 
-            if self.state.current_level >= self.state.max_level {
+            // the tick stands still while building, a stun must not outlast the wave
+            for turret in self.turret_state.iter_mut() {
+                turret.general_data.disabled_until = 0;
+            }
+
+            // a won game waits in the building phase, it can go on in endless mode
+            if !self.state.endless && self.state.current_level >= self.state.max_level {
                 self.state.still_running = false;
-                return;
             }
 
-            self.spawner.reset();
             self.spawner
-                .set_spawn(create_level(self.state.current_level));
+                .set_wave(self.create_wave(self.state.current_level));
             self.state.game_phase = GamePhase::Building;
             return;
         }
 
+        // Stealth creeps are only visible within the range of a detector
+        let detectors: Vec<(FloatPosition, f32)> = self
+            .turret_state
+            .iter()
+            .filter(|turret| turret.general_data.detector)
+            .map(|turret| {
+                (
+                    turret.general_data.get_float_pos(self.state.cell_length),
+                    turret.range() * self.state.cell_length,
+                )
+            })
+            .collect();
+        for creep in self.state.creeps.iter_mut() {
+            if let CreepKind::Stealth = creep.kind {
+                creep.visible = detectors
+                    .iter()
+                    .any(|(pos, range)| distance(*pos, creep.pos) <= *range);
+            }
+        }
+
         for turret in self.turret_state.iter_mut() {
             turret.tick(&mut self.state);
         }
@@ -488,7 +754,7 @@ impl Game {
         let mut creeps_to_remove: Vec<RecycledListRef> = vec![];
 
         let cell_length = self.state.cell_length;
-        let (particles, creeps, multi_particles, gold) = self.state.split_borrow();
+        let (particles, creeps, multi_particles) = self.state.split_borrow();
 
         for particle_item in particles.enumerate_mut() {
             let particle = &mut particle_item.data;
@@ -500,6 +766,12 @@ impl Game {
 
             let target_creep = target_creep_option.unwrap();
 
+            // particles aimed at a creep that burrowed fizzle
+            if target_creep.is_burrowed() {
+                particles_to_remove.push(particle_item.item_ref);
+                continue;
+            }
+
             let d = distance(target_creep.pos, particle.pos);
             if d < 5.0 {
                 particles_to_remove.push(particle_item.item_ref);
@@ -509,14 +781,20 @@ impl Game {
                     lifetime_in_ticks: 20,
                 });
 
+                // shells explode on the ground
                 for creep_in_radius_item in creeps.enumerate_mut().filter(|creep| {
                     distance(creep.data.pos, target_creep.pos)
                         <= particle.explosion_radius * cell_length
+                        && !creep.data.is_burrowed()
+                        && !(creep.data.is_flying()
+                            && particle.damage_type == DamageType::Explosive)
                 }) {
                     let creep_in_radius = &mut creep_in_radius_item.data;
-                    creep_in_radius.health -= particle.damage;
+                    creep_in_radius.take_damage(particle.damage, particle.damage_type);
+                    if let Some(poison) = particle.poison {
+                        creep_in_radius.apply_poison(poison);
+                    }
                     if creep_in_radius.health <= 0.0 {
-                        *gold += creep_in_radius.gold; // todo: gold per killed creep depending on level?
                         creeps_to_remove.push(creep_in_radius_item.item_ref);
                     }
                 }
@@ -534,20 +812,20 @@ impl Game {
             let particle = &mut particle_item.data;
             let mut best_distance: f32 = 10.0;
             let mut best_creep: Option<&mut RecycledListItem<Creep>> = None;
-            for creep_in_radius_item in creeps.enumerate_mut() {
+            for creep_in_radius_item in creeps
+                .enumerate_mut()
+                .filter(|creep| !creep.data.is_burrowed())
+            {
                 let d = distance(creep_in_radius_item.data.pos, particle.pos);
                 if d < best_distance {
                     best_creep = Some(creep_in_radius_item);
                     best_distance = d;
                 }
             }
-            if best_creep.is_some() {
-                let creep_item = best_creep.unwrap();
+            if let Some(creep) = best_creep {
                 multi_particles_to_remove.push(particle_item.item_ref);
-                let creep = creep_item;
-                creep.data.health -= particle.damage;
+                creep.data.take_damage(particle.damage, DamageType::Kinetic);
                 if creep.data.health <= 0.0 {
-                    *gold += creep.data.gold; // todo: gold per killed creep depending on level?
                     creeps_to_remove.push(creep.item_ref);
                 }
             }
@@ -563,7 +841,7 @@ impl Game {
 
         // Cleanup
         for creep in creeps_to_remove.iter() {
-            creeps.remove(*creep);
+            self.state.kill_creep(*creep);
         }
 
         for particle_to_remove in particles_to_remove.iter() {
@@ -571,6 +849,7 @@ impl Game {
         }
 
         update_particles_with_lifetime(&mut self.cannon_particles);
+        update_particles_with_lifetime(&mut self.teleport_particles);
         update_particles_with_lifetime(&mut self.state.sniper_particles);
         update_particles_with_lifetime(&mut self.state.multi_particles);
 
@@ -578,6 +857,235 @@ impl Game {
     }
 }
 
+impl Game {
+    fn create_wave(&self, level: u32) -> Wave {
+        let mut wave = if level >= self.state.max_level {
+            create_endless_level(&self.waves, level, self.state.max_level)
+        } else {
+            create_level(&self.waves, level)
+        };
+        self.settings.apply_to_wave(&mut wave);
+        wave
+    }
+
+    // every check of `build_tower` except for blocking the creep path
+    fn check_build(&self, x: f32, y: f32, kind: i32) -> Result<Turret, BuildError> {
+        if let GamePhase::Fighting = self.state.game_phase {
+            return Err(BuildError::FightingPhase);
+        }
+
+        let cost = tower_cost(kind).ok_or(BuildError::UnknownKind)?;
+
+        if self.state.gold < cost {
+            return Err(BuildError::NotEnoughGold);
+        }
+
+        if x < 0.0 || y < 0.0 {
+            return Err(BuildError::OutOfBounds);
+        }
+
+        let grid_pos = to_grid_position(FloatPosition { x, y }, self.state.cell_length);
+        if grid_pos.x >= self.state.board_dimension_x || grid_pos.y >= self.state.board_dimension_y
+        {
+            return Err(BuildError::OutOfBounds);
+        }
+
+        if self
+            .turret_state
+            .iter()
+            .any(|x| x.general_data.pos == grid_pos)
+        {
+            return Err(BuildError::Occupied);
+        }
+
+        Ok(Turret {
+            general_data: GeneralData {
+                pos: grid_pos,
+                last_shot: self.state.tick,
+                level: 0,
+                targeting: TargetingMode::Nearest,
+                detector: false,
+                disabled_until: 0,
+            },
+            specific_data: match kind {
+                0 => SpecificData::Basic(DynamicBasicData {
+                    rotation: 0.0,
+                    target: RecycledListRef::null_ref(),
+                }),
+                1 => SpecificData::Sniper(DynamicSniperData {
+                    rotation: 0.0,
+                    target: RecycledListRef::null_ref(),
+                    aiming_ticks: 0,
+                }),
+                2 => SpecificData::Cannon(DynamicCannonData {
+                    rotation: 0.0,
+                    target: RecycledListRef::null_ref(),
+                }),
+                3 => SpecificData::Multi(DynamicMultiData {
+                    rotation: 0.0,
+                    target: RecycledListRef::null_ref(),
+                }),
+                4 => SpecificData::Freeze(FREEZE[0]),
+                5 => SpecificData::Poison(DynamicPoisonData {
+                    rotation: 0.0,
+                    target: RecycledListRef::null_ref(),
+                }),
+                6 => SpecificData::Laser(DynamicLaserData {
+                    rotation: 0.0,
+                    target: RecycledListRef::null_ref(),
+                    beam_ticks: 0,
+                    firing: false,
+                }),
+                _ => return Err(BuildError::UnknownKind),
+            },
+        })
+    }
+
+    fn draw(&self, art: &impl Renderer, time: f32) {
+        let state = &self.state;
+
+        art.clear();
+        art.draw_map(
+            state.board_dimension_x as f32 * state.cell_length,
+            state.board_dimension_y as f32 * state.cell_length,
+        );
+
+        art.start_creep_path(state.creep_path[0].x, state.creep_path[0].y, time);
+        for line in &state.creep_path[1..] {
+            art.draw_creep_path_line(line.x, line.y);
+        }
+        art.end_creep_path();
+
+        let creep_spawn = to_float_position(state.creep_spawn, state.cell_length);
+        art.draw_creep_spawn(creep_spawn.x, creep_spawn.y, state.cell_length);
+
+        for goal in &state.creep_goals {
+            let creep_goal = to_float_position(*goal, state.cell_length);
+            art.draw_creep_goal(creep_goal.x, creep_goal.y, state.cell_length);
+        }
+
+        for turret in self.turret_state.iter() {
+            let external_turret = to_external_turret(turret, state);
+            art.set_alpha(if external_turret.disabled { 0.4 } else { 1.0 });
+            art.draw_turret(
+                external_turret.pos.x,
+                external_turret.pos.y,
+                external_turret.rotation,
+                state.cell_length,
+                external_turret.kind,
+            );
+            art.draw_turret_range(
+                external_turret.pos.x + state.cell_length / 2.0,
+                external_turret.pos.y + state.cell_length / 2.0,
+                external_turret.range,
+            );
+        }
+        art.set_alpha(1.0);
+
+        // lasers hit every tick, the beam gets wider while the damage ramps up
+        for turret in self.turret_state.iter() {
+            let SpecificData::Laser(laser) = &turret.specific_data else {
+                continue;
+            };
+            if !laser.firing || turret.general_data.is_disabled(state.tick) {
+                continue;
+            }
+            if let Some(target) = state.creeps.get(laser.target) {
+                let pos = turret.general_data.get_float_pos(state.cell_length);
+                let level = turret.general_data.level;
+                art.draw_laser_beam(
+                    pos.x,
+                    pos.y,
+                    target.pos.x,
+                    target.pos.y,
+                    (laser.damage_multiplier(level) - 1.0)
+                        / (LASER[level as usize].max_multiplier - 1.0),
+                );
+            }
+        }
+
+        for particle in self.cannon_particles.iter() {
+            art.draw_cannon_particle(
+                particle.pos.x,
+                particle.pos.y,
+                particle.explosion_radius
+                    * self.state.cell_length
+                    * particle.lifetime_in_ticks as f32
+                    / 20.0,
+            );
+        }
+
+        // a ring that shrinks at both ends of the teleport
+        for particle in self.teleport_particles.iter() {
+            art.draw_teleport_particle(
+                particle.pos.x,
+                particle.pos.y,
+                self.state.cell_length / 2.0 * particle.lifetime_in_ticks as f32
+                    / TELEPORT_EFFECT_LIFETIME_IN_TICKS as f32,
+            );
+        }
+
+        for particle in self.state.sniper_particles.iter() {
+            art.draw_sniper_particle(
+                particle.start_pos.x,
+                particle.start_pos.y,
+                particle.target_pos.x,
+                particle.target_pos.y,
+            );
+        }
+
+        for creep in state.creeps.iter() {
+            art.set_alpha(if creep.visible { 1.0 } else { 0.4 });
+            art.draw_creep(
+                creep.pos.x,
+                creep.pos.y,
+                creep.health / creep.max_health,
+                creep
+                    .shield
+                    .map_or(0.0, |shield| shield.value / shield.max_value),
+                creep.is_burrowed(),
+                creep.kind,
+            );
+        }
+        art.set_alpha(1.0);
+
+        for particle in state.particles.iter() {
+            art.draw_particle(particle.pos.x, particle.pos.y);
+        }
+
+        for particle in state.multi_particles.iter() {
+            art.draw_particle(particle.pos.x, particle.pos.y);
+        }
+    }
+}
+
+fn tower_cost(kind: i32) -> Option<u32> {
+    match kind {
+        0 => Some(BASIC[0].cost),
+        1 => Some(SNIPER[0].cost),
+        2 => Some(CANNON[0].cost),
+        3 => Some(MULTI[0].cost),
+        4 => Some(FREEZE[0].cost),
+        5 => Some(POISON[0].cost),
+        6 => Some(LASER[0].cost),
+        _ => None,
+    }
+}
+
+// in tiles
+fn tower_range(kind: i32) -> Option<f32> {
+    match kind {
+        0 => Some(BASIC[0].range),
+        1 => Some(SNIPER[0].range),
+        2 => Some(CANNON[0].range),
+        3 => Some(MULTI[0].range),
+        4 => Some(FREEZE[0].range),
+        5 => Some(POISON[0].range),
+        6 => Some(LASER[0].range),
+        _ => None,
+    }
+}
+
 fn update_particles_with_lifetime<T: Clone + ParticleWithLifetime>(
     particles: &mut RecycledList<T>,
 ) {
@@ -615,6 +1123,7 @@ pub struct State {
     pub creep_spawn: GridPosition,
     pub creep_goals: Vec<GridPosition>,
     pub creep_path: Vec<FloatPosition>,
+    pub flying_path: Vec<FloatPosition>, // creep spawn and goals
     pub creeps: RecycledList<Creep>,
     pub particles: RecycledList<Particle>,
     pub sniper_particles: RecycledList<SniperParticle>,
@@ -624,6 +1133,12 @@ pub struct State {
     pub still_running: bool,
     pub current_level: u32,
     pub max_level: u32,
+    pub endless: bool,
+    pub highest_wave: u32, // score in endless mode
+    pub leak_damage_total: f32,
+    pub wave_leak_damage: f32,
+    pub wave_spawned_creeps: u32, // every creep added in the current wave, minions and splitlings too
+    pub events: EventQueue,
     game_phase: GamePhase,
     gold: u32,
 
@@ -631,19 +1146,609 @@ pub struct State {
 }
 
 impl State {
+    pub fn path_for(&self, creep: &Creep) -> &Vec<FloatPosition> {
+        if creep.is_flying() {
+            &self.flying_path
+        } else {
+            &self.creep_path
+        }
+    }
+
+    // index into creep_path of the creep spawn (0) or the n-th creep goal
+    pub fn waypoint_path_index(&self, waypoint: u32) -> usize {
+        let mut index = 0;
+        // a creep starting at the last goal would leak immediately
+        let waypoint = u32::min(waypoint, self.creep_goals.len() as u32 - 1);
+        for goal in self.creep_goals.iter().take(waypoint as usize) {
+            match self.creep_path[index..]
+                .iter()
+                .position(|pos| to_grid_position(*pos, self.cell_length) == *goal)
+            {
+                Some(offset) => index += offset,
+                None => return 0,
+            }
+        }
+        index
+    }
+
+    // every killed creep goes through here, a creep can only die once
+    pub fn kill_creep(&mut self, creep_ref: RecycledListRef) {
+        let creep = match self.creeps.get(creep_ref) {
+            Some(creep) => *creep,
+            None => return,
+        };
+
+        self.gold += creep.gold; // todo: gold per killed creep depending on level?
+        self.creeps.remove(creep_ref);
+        for child in creep.on_death() {
+            self.add_creep(child);
+        }
+    }
+
+    // every new creep goes through here, so the wave statistics include minions and splitlings
+    pub fn add_creep(&mut self, creep: Creep) {
+        self.wave_spawned_creeps += 1;
+        self.creeps.add(creep);
+    }
+
     fn split_borrow(
         &mut self,
     ) -> (
         &mut RecycledList<Particle>,
         &mut RecycledList<Creep>,
         &mut RecycledList<MultiParticle>,
-        &mut u32,
     ) {
         (
             &mut self.particles,
             &mut self.creeps,
             &mut self.multi_particles,
-            &mut self.gold,
         )
     }
 }
+
+// next to the creep spawn of the default map
+#[cfg(test)]
+const TEST_TOWER_CELL: GridPosition = GridPosition { x: 3, y: 1 };
+
+#[cfg(test)]
+impl Game {
+    // a fighting game whose only wave are the `groups`
+    fn start_test_wave(groups: &str) -> Game {
+        Game::start_test_wave_with(groups, |_| {})
+    }
+
+    // `prepare` runs while building, e.g. to place towers
+    fn start_test_wave_with(groups: &str, prepare: impl FnOnce(&mut Game)) -> Game {
+        let mut game = Game::new();
+        game.load_wave_script(&format!("wave\n{groups}\n")).unwrap();
+        prepare(&mut game);
+        game.start_wave();
+        game
+    }
+
+    fn build_test_tower(&mut self, kind: i32) {
+        let pos = to_creep_position(TEST_TOWER_CELL, self.state.cell_length);
+        self.build_tower(pos.x, pos.y, kind);
+    }
+
+    fn test_tower(&self) -> RecycledListRef {
+        self.turret_state
+            .enumerate()
+            .find(|turret| turret.data.general_data.pos == TEST_TOWER_CELL)
+            .unwrap()
+            .item_ref
+    }
+
+    fn update_until(&mut self, done: impl Fn(&Game) -> bool) {
+        for _ in 0..100_000 {
+            if done(self) {
+                return;
+            }
+            self.update_state();
+        }
+        panic!("condition not reached");
+    }
+}
+
+#[test]
+fn test_preview_build() {
+    let mut game = Game::new();
+
+    let preview = game.preview_build(75.0, 75.0, 0);
+    assert!(preview.valid);
+    assert_eq!(preview.cost, BASIC[0].cost);
+    assert_eq!(game.state.gold, 200);
+    assert!(game.turret_state.is_empty());
+
+    game.build_tower(75.0, 75.0, 0);
+    let preview = game.preview_build(75.0, 75.0, 1);
+    assert!(!preview.valid);
+    assert_eq!(preview.reason, BuildError::Occupied);
+
+    assert_eq!(
+        game.preview_build(75.0, 75.0, 42).reason,
+        BuildError::UnknownKind
+    );
+    assert_eq!(
+        game.preview_build(-1.0, 75.0, 0).reason,
+        BuildError::OutOfBounds
+    );
+}
+
+#[test]
+fn test_get_creeps() {
+    let mut game = Game::new();
+    game.start_wave();
+    for _ in 0..100 {
+        game.update_state();
+    }
+
+    let creeps = game.get_creeps();
+    assert_eq!(creeps.len(), 1);
+
+    let creep = game.get_creep_at(creeps[0].pos.x, creeps[0].pos.y).unwrap();
+    assert_eq!(creep.creep_ref.id, creeps[0].creep_ref.id);
+    assert_eq!(creep.health, creep.max_health);
+    assert!(creep.progress > 0.0 && creep.progress < 1.0);
+    assert!(creep.distance_to_goal > 0.0);
+    assert!(game.get_creep_at(-100.0, -100.0).is_none());
+}
+
+#[test]
+fn test_upcoming_waves() {
+    let mut game = Game::new();
+
+    let waves = game.get_upcoming_waves(3);
+    assert_eq!(waves.len(), 3);
+    assert_eq!(waves[1].level, 1);
+    assert!(matches!(waves[1].groups[0].kind, CreepKind::Grouped));
+    assert_eq!(
+        waves[0].groups[0].quantity,
+        create_level(&game.waves, 0).spawns[0].quantity
+    );
+
+    game.state.current_level = game.state.max_level - 1;
+    assert_eq!(game.get_upcoming_waves(3).len(), 1);
+
+    game.set_endless(true);
+    assert_eq!(game.get_upcoming_waves(3).len(), 3);
+}
+
+#[test]
+fn test_endless_after_win() {
+    let mut game =
+        Game::start_test_wave_with("group kind=Normal count=1 health=100 bounty=1", |game| {
+            game.state.current_level = game.state.max_level - 1
+        });
+    game.update_until(|game| !game.state.creeps.is_empty());
+    let creep = game.state.creeps.enumerate().next().unwrap().item_ref;
+    game.state.kill_creep(creep);
+    game.update_state();
+    assert!(matches!(
+        game.get_state().game_result,
+        GameResult::PlayerWon
+    ));
+    assert!(matches!(game.state.game_phase, GamePhase::Building));
+    game.start_wave();
+    assert!(matches!(game.state.game_phase, GamePhase::Building));
+
+    // the won game goes on with generated waves
+    game.set_endless(true);
+    assert!(matches!(
+        game.get_state().game_result,
+        GameResult::StillRunning
+    ));
+    game.start_wave();
+    game.update_until(|game| !game.state.creeps.is_empty());
+    assert_eq!(game.state.highest_wave, game.state.max_level + 1);
+}
+
+#[test]
+fn test_healer() {
+    let mut game = Game::start_test_wave(
+        "group kind=Normal count=1 health=100 bounty=1\n\
+         group kind=Healer count=1 health=50 bounty=1 parallel=true",
+    );
+    game.update_until(|game| game.state.creeps.iter().count() == 2);
+
+    for creep in game.state.creeps.iter_mut() {
+        creep.health = creep.max_health / 2.0;
+    }
+    for _ in 0..HEALER_INTERVAL_IN_TICKS {
+        game.update_state();
+    }
+
+    for creep in game.state.creeps.iter() {
+        match creep.kind {
+            CreepKind::Healer => assert_eq!(creep.health, 25.0),
+            _ => assert_eq!(creep.health, 50.0 + HEALER_HEAL_PERCENT),
+        }
+    }
+}
+
+#[test]
+fn test_splitter() {
+    let mut game = Game::start_test_wave("group kind=Splitter count=1 health=100 bounty=9");
+    for _ in 0..200 {
+        game.update_state();
+    }
+
+    let splitter = game.state.creeps.enumerate().next().unwrap().clone();
+    let gold = game.state.gold;
+    game.state.kill_creep(splitter.item_ref);
+    game.state.kill_creep(splitter.item_ref);
+    assert_eq!(game.state.gold, gold + 9);
+
+    let children: Vec<&Creep> = game.state.creeps.iter().collect();
+    assert_eq!(children.len(), SPLITTER_CHILDREN as usize);
+    assert_eq!(game.state.wave_spawned_creeps, 1 + SPLITTER_CHILDREN);
+    for child in children {
+        assert!(matches!(child.kind, CreepKind::Splitling));
+        assert!((child.max_health - 30.0).abs() < 0.01);
+        assert_eq!(
+            child.walking.current_goal,
+            splitter.data.walking.current_goal
+        );
+    }
+}
+
+#[test]
+fn test_stealth_and_detector() {
+    for detector in [false, true] {
+        let mut game =
+            Game::start_test_wave_with("group kind=Stealth count=1 health=1000 bounty=1", |game| {
+                game.build_test_tower(0);
+                if detector {
+                    let tower = game.test_tower();
+                    game.buy_detector(tower.id, tower.index);
+                    assert_eq!(game.state.gold, 200 - BASIC[0].cost - DETECTOR_COST);
+                }
+            });
+        let tower = game.test_tower();
+        for _ in 0..70 {
+            game.update_state();
+        }
+
+        assert_eq!(game.state.creeps.iter().next().unwrap().visible, detector);
+        match game.turret_state.get(tower).unwrap().specific_data {
+            SpecificData::Basic(data) => {
+                assert_eq!(game.state.creeps.get(data.target).is_some(), detector)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn test_flying() {
+    let mut game =
+        Game::start_test_wave_with("group kind=Flying count=1 health=1000 bounty=1", |game| {
+            game.build_test_tower(2)
+        });
+    let cannon = game.test_tower();
+    for _ in 0..70 {
+        game.update_state();
+    }
+
+    let creep = game.get_creeps()[0];
+    assert!(creep.progress > 0.0);
+    assert!(creep.distance_to_goal < 105.0);
+    match game.turret_state.get(cannon).unwrap().specific_data {
+        SpecificData::Cannon(data) => assert!(game.state.creeps.get(data.target).is_none()),
+        _ => unreachable!(),
+    }
+
+    // 105 tiles along the waypoints at 1 tile/s
+    let mut ticks = 70;
+    while game.state.health == 10 {
+        game.update_state();
+        ticks += 1;
+    }
+    assert!((ticks as f32 - 60.0 - 105.0 * 60.0).abs() < 60.0);
+}
+
+#[test]
+fn test_leak_damage() {
+    let mut game = Game::new_with_settings(GameSettings {
+        milestone_lives: 2,
+        ..GameSettings::default()
+    });
+    game.state.current_level = BOSS_WAVE_INTERVAL - 1;
+    game.load_wave_script(
+        "wave\n\
+         group kind=Big count=1 health=1 bounty=1 speed=10\n\
+         group kind=Grouped count=3 health=1 bounty=1 speed=10 parallel=true\n",
+    )
+    .unwrap();
+    game.start_wave();
+    while let GamePhase::Fighting = game.state.game_phase {
+        game.update_state();
+    }
+
+    // 2 + 3 * 0.5 lives leaked, then 2 regained at the milestone
+    assert_eq!(game.state.wave_leak_damage, 3.5);
+    assert_eq!(game.state.health, 10 - 3 + 2);
+
+    let events = game.take_events();
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[0].kind, GameEventKind::CreepLeaked);
+    assert_eq!(events[4].kind, GameEventKind::LivesRegained);
+    assert_eq!(events[4].value, 2.0);
+    assert!(game.take_events().is_empty());
+}
+
+#[test]
+fn test_emp() {
+    let mut game = Game::start_test_wave_with(
+        "group kind=Emp count=1 health=100000 bounty=1 speed=0.1",
+        |game| game.build_test_tower(0),
+    );
+    let tower = game.test_tower();
+    game.update_until(|game| {
+        game.state
+            .creeps
+            .iter()
+            .any(|creep| creep.ticks_alive >= EMP_INTERVAL_IN_TICKS)
+    });
+
+    let turret = game.turret_state.get(tower).unwrap();
+    assert!(to_external_turret(turret, &game.state).disabled);
+    let last_shot = turret.general_data.last_shot;
+    for _ in 0..EMP_DURATION_IN_TICKS - 1 {
+        game.update_state();
+    }
+    assert_eq!(
+        game.turret_state.get(tower).unwrap().general_data.last_shot,
+        last_shot
+    );
+    game.update_state();
+    assert!(!game
+        .turret_state
+        .get(tower)
+        .unwrap()
+        .general_data
+        .is_disabled(game.state.tick));
+
+    let events = game.take_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, GameEventKind::EmpPulse);
+
+    // a stun ends with the wave
+    let turret = game.turret_state.get_mut(tower).unwrap();
+    turret.general_data.disabled_until = game.state.tick + EMP_DURATION_IN_TICKS;
+    let creep = game.state.creeps.enumerate().next().unwrap().item_ref;
+    game.state.kill_creep(creep);
+    game.update_state();
+    assert!(game.state.creeps.is_empty());
+    assert!(!game
+        .turret_state
+        .get(tower)
+        .unwrap()
+        .general_data
+        .is_disabled(game.state.tick));
+}
+
+#[test]
+fn test_carrier() {
+    let mut game =
+        Game::start_test_wave("group kind=Carrier count=1 health=100 bounty=40 speed=0.1");
+    game.update_until(|game| {
+        game.state
+            .creeps
+            .iter()
+            .any(|creep| creep.ticks_alive >= CARRIER_DROP_INTERVAL_IN_TICKS)
+    });
+    // the spawner runs before the carrier's ticks are counted
+    game.update_state();
+    assert!(game.spawner.is_finished());
+    assert_eq!(game.state.wave_spawned_creeps, 2);
+
+    let (carrier, minion) = {
+        let mut creeps = game.state.creeps.iter();
+        (*creeps.next().unwrap(), *creeps.next().unwrap())
+    };
+    assert!(matches!(minion.kind, CreepKind::Minion));
+    assert_eq!(minion.walking.current_goal, carrier.walking.current_goal);
+    assert_eq!(minion.walking.progress_made, carrier.walking.progress_made);
+    assert_eq!(minion.max_health, 100.0 * CARRIER_MINION_HEALTH);
+    assert_eq!(minion.gold, carrier.gold / CARRIER_MAX_DROPS);
+
+    // the wave only ends once the dropped minions are gone too
+    let carrier_ref = game.state.creeps.enumerate().next().unwrap().item_ref;
+    game.state.kill_creep(carrier_ref);
+    game.update_state();
+    assert!(matches!(game.state.game_phase, GamePhase::Fighting));
+}
+
+#[test]
+fn test_burrowing() {
+    let mut game = Game::start_test_wave_with(
+        "group kind=Burrowing count=1 health=100000 bounty=1 speed=0.05",
+        |game| game.build_test_tower(0),
+    );
+    game.update_until(|game| !game.state.particles.is_empty());
+    let creep = game.state.creeps.enumerate().next().unwrap().item_ref;
+    game.state.creeps.get_mut(creep).unwrap().ticks_alive =
+        BURROW_INTERVAL_IN_TICKS - BURROW_DURATION_IN_TICKS - 1;
+    game.update_state();
+    // the particle in flight fizzles and the tower can't pick a new target
+    assert!(game.state.particles.is_empty());
+    let external = to_external_creep(game.state.creeps.get(creep).unwrap(), creep, &game.state);
+    assert!(external.burrowed);
+
+    for _ in 0..BURROW_DURATION_IN_TICKS - 1 {
+        game.update_state();
+        assert!(game.state.particles.is_empty());
+    }
+    game.update_state();
+    assert!(!game.state.creeps.get(creep).unwrap().is_burrowed());
+
+    // a sniper has to aim again at a creep that resurfaced
+    let mut game = Game::start_test_wave_with(
+        "group kind=Burrowing count=1 health=100000 bounty=1 speed=0.05",
+        |game| game.build_test_tower(1),
+    );
+    let tower = game.test_tower();
+    let sniper = |game: &Game| match game.turret_state.get(tower).unwrap().specific_data {
+        SpecificData::Sniper(data) => data,
+        _ => unreachable!(),
+    };
+    game.update_until(|game| sniper(game).aiming_ticks > 10);
+    let creep = game.state.creeps.enumerate().next().unwrap().item_ref;
+    game.state.creeps.get_mut(creep).unwrap().ticks_alive =
+        BURROW_INTERVAL_IN_TICKS - BURROW_DURATION_IN_TICKS - 1;
+    for _ in 0..BURROW_DURATION_IN_TICKS {
+        game.update_state();
+        assert_eq!(sniper(&game).aiming_ticks, 0);
+    }
+    game.update_state();
+    assert!(!game.state.creeps.get(creep).unwrap().is_burrowed());
+    assert_eq!(sniper(&game).aiming_ticks, 0);
+}
+
+#[test]
+fn test_teleporting() {
+    let mut game =
+        Game::start_test_wave("group kind=Teleporting count=1 health=100 bounty=1 speed=0.001");
+    game.update_until(|game| {
+        game.state
+            .creeps
+            .iter()
+            .any(|creep| creep.ticks_alive >= TELEPORT_INTERVAL_IN_TICKS)
+    });
+    let creep = *game.state.creeps.iter().next().unwrap();
+    assert_eq!(creep.walking.current_goal, TELEPORT_CELLS);
+    assert!(distance(creep.pos, game.state.creep_path[TELEPORT_CELLS as usize]) < 1.0);
+
+    let events = game.take_events();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].kind, GameEventKind::TeleportOut);
+    assert!(distance(events[0].pos, game.state.creep_path[0]) < 1.0);
+    assert_eq!(events[1].kind, GameEventKind::TeleportIn);
+    assert_eq!(events[1].value, TELEPORT_CELLS as f32);
+    assert_eq!(game.teleport_particles.iter().count(), 2);
+    assert!(game.to_svg().contains("stroke=\"violet\""));
+
+    // only a few teleports per life
+    for _ in 0..TELEPORT_INTERVAL_IN_TICKS * (TELEPORTS_PER_LIFE + 1) {
+        game.update_state();
+    }
+    assert_eq!(
+        game.take_events().len(),
+        2 * (TELEPORTS_PER_LIFE as usize - 1)
+    );
+}
+
+#[test]
+fn test_poison_turret() {
+    let mut game = Game::start_test_wave_with(
+        "group kind=Normal count=1 health=100000 bounty=1 speed=0.01",
+        |game| game.build_test_tower(5),
+    );
+    let stacks = |game: &Game| {
+        game.state
+            .creeps
+            .iter()
+            .next()
+            .and_then(|creep| creep.poison)
+            .map_or(0, |poison| poison.stacks)
+    };
+    game.update_until(|game| stacks(game) == 1);
+    game.update_until(|game| stacks(game) == 2);
+
+    // more hits than the cap only refresh the duration
+    let cooldown = (60.0 / POISON[0].attack_speed) as u32;
+    for _ in 0..cooldown * (POISON[0].max_stacks + 3) {
+        game.update_state();
+        assert!(stacks(&game) <= POISON[0].max_stacks);
+    }
+    assert_eq!(stacks(&game), POISON[0].max_stacks);
+    let creep = game.state.creeps.iter().next().unwrap();
+    assert!(creep.health < creep.max_health);
+}
+
+#[test]
+fn test_laser() {
+    let mut game = Game::start_test_wave_with(
+        "group kind=Normal count=2 interval=30 health=100000 bounty=1 speed=0.01",
+        |game| game.build_test_tower(6),
+    );
+    let tower = game.test_tower();
+    let laser = |game: &Game| match game.turret_state.get(tower).unwrap().specific_data {
+        SpecificData::Laser(data) => data,
+        _ => unreachable!(),
+    };
+    game.update_until(|game| laser(game).firing);
+    assert!(game.to_svg().contains("stroke=\"magenta\""));
+
+    // the damage ramps up while the laser stays on the same creep
+    let damage_per_tick = |game: &mut Game| {
+        let target = laser(game).target;
+        let health = game.state.creeps.get(target).unwrap().health;
+        game.update_state();
+        health - game.state.creeps.get(target).unwrap().health
+    };
+    let base_damage = LASER[0].damage / 60.0;
+    let first = damage_per_tick(&mut game);
+    for _ in 0..(LASER[0].ramp_time * 60.0) as u32 {
+        game.update_state();
+    }
+    let last = damage_per_tick(&mut game);
+    assert!((first - base_damage).abs() < 0.01);
+    assert!((last - base_damage * LASER[0].max_multiplier).abs() < 0.01);
+
+    // an EMP stun stops the beam, it ramps up again afterwards
+    let turret = game.turret_state.get_mut(tower).unwrap();
+    turret.general_data.disabled_until = game.state.tick + 10;
+    game.update_state();
+    assert_eq!(laser(&game).beam_ticks, 0);
+    assert!(!laser(&game).firing);
+    game.update_until(|game| laser(game).firing);
+    assert!((damage_per_tick(&mut game) - base_damage).abs() < 0.01);
+
+    // a new target starts with the base damage as well
+    for _ in 0..(LASER[0].ramp_time * 60.0) as u32 {
+        game.update_state();
+    }
+    let first_target = laser(&game).target;
+    game.state.kill_creep(first_target);
+    game.update_state();
+    game.update_until(|game| laser(game).firing);
+    assert!(laser(&game).target != first_target);
+    assert!((damage_per_tick(&mut game) - base_damage).abs() < 0.01);
+}
+
+#[test]
+fn test_laser_on_moving_creep() {
+    let mut game = Game::start_test_wave_with(
+        "group kind=Normal count=1 interval=30 health=100000 bounty=1 speed=1.0",
+        |game| {
+            game.build_test_tower(6);
+            // facing the spawn, so the laser locks on as soon as the creep is in range
+            let tower = game.test_tower();
+            let diff = to_creep_position(game.state.creep_spawn, game.state.cell_length)
+                - to_creep_position(TEST_TOWER_CELL, game.state.cell_length);
+            if let SpecificData::Laser(data) =
+                &mut game.turret_state.get_mut(tower).unwrap().specific_data
+            {
+                data.rotation = f32::atan2(diff.y, diff.x);
+            }
+        },
+    );
+    let tower = game.test_tower();
+    let laser = |game: &Game| match game.turret_state.get(tower).unwrap().specific_data {
+        SpecificData::Laser(data) => data,
+        _ => unreachable!(),
+    };
+    game.update_until(|game| laser(game).firing);
+
+    // the beam stays on while the turret turns after the creep
+    let target = laser(&game).target;
+    let health = game.state.creeps.get(target).unwrap().health;
+    for _ in 0..30 {
+        game.update_state();
+    }
+    assert!(laser(&game).firing);
+    assert_eq!(laser(&game).beam_ticks, 31);
+    assert!(game.state.creeps.get(target).unwrap().health < health);
+}
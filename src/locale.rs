@@ -0,0 +1,87 @@
+use wasm_bindgen::prelude::*;
+
+use crate::external::{StatId, StatUnit};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Locale {
+    En,
+    De,
+    Fr,
+}
+
+// (en, de, fr)
+fn stat_names(id: StatId) -> (&'static str, &'static str, &'static str) {
+    match id {
+        StatId::Level => ("Level", "Stufe", "Niveau"),
+        StatId::Range => ("Range", "Reichweite", "Portée"),
+        StatId::Damage => ("Damage", "Schaden", "Dégâts"),
+        StatId::AttackSpeed => (
+            "Attack speed",
+            "Angriffsgeschwindigkeit",
+            "Vitesse d'attaque",
+        ),
+        StatId::RotationSpeed => (
+            "Rotation speed",
+            "Drehgeschwindigkeit",
+            "Vitesse de rotation",
+        ),
+        StatId::ProjectileSpeed => (
+            "Projectile speed",
+            "Projektilgeschwindigkeit",
+            "Vitesse des projectiles",
+        ),
+        StatId::AimingSpeed => ("Aiming speed", "Zielgeschwindigkeit", "Vitesse de visée"),
+        StatId::FreezePercent => ("Freeze percent", "Verlangsamung", "Ralentissement"),
+        StatId::FreezeSpeed => ("Freeze speed", "Frostgeschwindigkeit", "Vitesse de gel"),
+        StatId::PoisonDamage => ("Poison damage", "Giftschaden", "Dégâts de poison"),
+        StatId::PoisonDuration => ("Poison duration", "Giftdauer", "Durée du poison"),
+        StatId::PoisonStacks => ("Poison stacks", "Giftstapel", "Cumuls de poison"),
+        StatId::RampMultiplier => (
+            "Max. damage multiplier",
+            "Max. Schadensfaktor",
+            "Multiplicateur max.",
+        ),
+        StatId::RampTime => ("Ramp-up time", "Aufladezeit", "Temps de charge"),
+        StatId::Cost => ("Cost", "Kosten", "Coût"),
+    }
+}
+
+// (en, de, fr)
+fn unit_names(unit: StatUnit) -> (&'static str, &'static str, &'static str) {
+    match unit {
+        StatUnit::None => ("", "", ""),
+        StatUnit::Tiles => ("tiles", "Felder", "cases"),
+        StatUnit::PerSecond => ("/s", "/s", "/s"),
+        StatUnit::PercentPerSecond => ("%/s", "%/s", "%/s"),
+        StatUnit::DegreesPerSecond => ("deg/s", "Grad/s", "deg/s"),
+        StatUnit::TilesPerSecond => ("tiles/s", "Felder/s", "cases/s"),
+        StatUnit::Seconds => ("s", "s", "s"),
+        StatUnit::Gold => ("gold", "Gold", "or"),
+    }
+}
+
+fn localize(names: (&'static str, &'static str, &'static str), locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => names.0,
+        Locale::De => names.1,
+        Locale::Fr => names.2,
+    }
+}
+
+#[wasm_bindgen]
+pub fn stat_label(id: StatId, locale: Locale) -> String {
+    String::from(localize(stat_names(id), locale))
+}
+
+#[wasm_bindgen]
+pub fn unit_label(unit: StatUnit, locale: Locale) -> String {
+    String::from(localize(unit_names(unit), locale))
+}
+
+#[test]
+fn test_labels() {
+    assert_eq!(stat_label(StatId::AttackSpeed, Locale::En), "Attack speed");
+    assert_eq!(stat_label(StatId::Range, Locale::De), "Reichweite");
+    assert_eq!(unit_label(StatUnit::Gold, Locale::Fr), "or");
+}
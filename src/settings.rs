@@ -0,0 +1,113 @@
+use wasm_bindgen::prelude::*;
+
+use crate::spawn::Wave;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct GameSettings {
+    pub difficulty: Difficulty,
+    pub starting_gold: u32,
+    pub lives: u32,
+    pub creep_health_multiplier: f32,
+    pub creep_speed_multiplier: f32,
+    pub bounty_multiplier: f32,
+    pub sell_refund_percent: u32, // of the build cost
+    pub milestone_lives: u32,     // regained after every 10th wave, up to `lives`
+}
+
+#[wasm_bindgen]
+impl GameSettings {
+    /// Built-in settings of a difficulty, `Normal` is the classic game.
+    pub fn preset(difficulty: Difficulty) -> GameSettings {
+        match difficulty {
+            Difficulty::Easy => GameSettings {
+                difficulty,
+                starting_gold: 300,
+                lives: 20,
+                creep_health_multiplier: 0.8,
+                creep_speed_multiplier: 1.0,
+                bounty_multiplier: 1.2,
+                sell_refund_percent: 100,
+                milestone_lives: 3,
+            },
+            Difficulty::Normal => GameSettings {
+                difficulty,
+                starting_gold: 200,
+                lives: 10,
+                creep_health_multiplier: 1.0,
+                creep_speed_multiplier: 1.0,
+                bounty_multiplier: 1.0,
+                sell_refund_percent: 100,
+                milestone_lives: 0,
+            },
+            Difficulty::Hard => GameSettings {
+                difficulty,
+                starting_gold: 150,
+                lives: 5,
+                creep_health_multiplier: 1.3,
+                creep_speed_multiplier: 1.1,
+                bounty_multiplier: 0.9,
+                sell_refund_percent: 75,
+                milestone_lives: 0,
+            },
+            Difficulty::Nightmare => GameSettings {
+                difficulty,
+                starting_gold: 100,
+                lives: 1,
+                creep_health_multiplier: 1.7,
+                creep_speed_multiplier: 1.2,
+                bounty_multiplier: 0.75,
+                sell_refund_percent: 50,
+                milestone_lives: 0,
+            },
+        }
+    }
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        GameSettings::preset(Difficulty::Normal)
+    }
+}
+
+impl GameSettings {
+    pub fn apply_to_wave(&self, wave: &mut Wave) {
+        for spawn in wave.spawns.iter_mut() {
+            spawn.health *= self.creep_health_multiplier;
+            spawn.speed *= self.creep_speed_multiplier;
+            spawn.bounty = (spawn.bounty as f32 * self.bounty_multiplier).round() as u32;
+        }
+    }
+
+    pub fn refund(&self, cost: u32) -> u32 {
+        cost * self.sell_refund_percent / 100
+    }
+}
+
+#[test]
+fn test_presets() {
+    let game = crate::Game::new();
+    let normal = GameSettings::default();
+    assert_eq!(game.state.gold, normal.starting_gold);
+    assert_eq!(game.state.health, normal.lives);
+    assert_eq!(normal.refund(25), 25);
+
+    let hard = crate::Game::new_with_settings(GameSettings::preset(Difficulty::Hard));
+    assert_eq!(hard.state.gold, 150);
+    assert_eq!(hard.state.health, 5);
+    let normal_wave = game.create_wave(0);
+    let hard_wave = hard.create_wave(0);
+    assert!(hard_wave.spawns[0].health > normal_wave.spawns[0].health);
+    assert!(hard_wave.spawns[0].speed > normal_wave.spawns[0].speed);
+    assert!(hard_wave.spawns[0].bounty <= normal_wave.spawns[0].bounty);
+    assert_eq!(hard.settings.refund(100), 75);
+}
@@ -1,74 +1,304 @@
-use crate::{utils::FloatPosition, Creep, CreepKind, WalkingProgress};
+use crate::{
+    boss::BossData, utils::Random, Creep, CreepKind, Shield, State, WalkingProgress,
+    CARRIER_DROP_INTERVAL_IN_TICKS, CARRIER_MAX_DROPS, CARRIER_MINION_HEALTH,
+};
+
+#[derive(Clone, Copy)]
+pub enum SpawnPattern {
+    Fixed,
+    // `size` creeps with `distance_in_ticks` between them, then a pause
+    Burst { size: u32, pause_in_ticks: u32 },
+    // every distance is randomly prolonged by up to `max_jitter_in_ticks`
+    Jitter { max_jitter_in_ticks: u32, seed: u32 },
+    // the distance changes linearly towards `final_distance_in_ticks` for the last creep
+    Ramp { final_distance_in_ticks: u32 },
+}
 
 #[derive(Clone)]
 pub struct Spawn {
     pub quantity: u32,
     pub distance_in_ticks: u32,
+    pub delay_in_ticks: u32, // additional ticks before the first creep
+    pub pattern: SpawnPattern,
+    pub parallel: bool, // start together with the previous spawn instead of after it
     pub health: f32,
     pub speed: f32,
-    pub bounty: u32, // bounty for the whole spawn. per creep this is bounty / quantity
+    pub bounty: u32, // per creep
     pub kind: CreepKind,
+    pub waypoint: u32,    // 0 = creep spawn, n = n-th creep goal
+    pub leak_damage: f32, // lives lost per creep reaching the last goal
+}
+
+impl Spawn {
+    // ticks until the next creep, after `spawned_creeps` creeps of this spawn
+    fn distance_in_ticks(&self, spawned_creeps: u32, random: &mut Random) -> u32 {
+        match self.pattern {
+            SpawnPattern::Fixed => self.distance_in_ticks,
+            SpawnPattern::Burst {
+                size,
+                pause_in_ticks,
+            } => {
+                if spawned_creeps > 0 && spawned_creeps.is_multiple_of(size) {
+                    pause_in_ticks
+                } else {
+                    self.distance_in_ticks
+                }
+            }
+            SpawnPattern::Jitter {
+                max_jitter_in_ticks,
+                ..
+            } => self.distance_in_ticks + random.next_up_to(max_jitter_in_ticks),
+            SpawnPattern::Ramp {
+                final_distance_in_ticks,
+            } => {
+                if self.quantity <= 1 {
+                    return self.distance_in_ticks;
+                }
+                let t = spawned_creeps as f32 / (self.quantity - 1) as f32;
+                (self.distance_in_ticks as f32 * (1.0 - t) + final_distance_in_ticks as f32 * t)
+                    .round() as u32
+            }
+        }
+    }
+}
+
+// a wave consists of spawns which are emitted one after another or in parallel
+#[derive(Clone)]
+pub struct Wave {
+    pub spawns: Vec<Spawn>,
+}
+
+#[derive(Clone, Copy)]
+struct SpawnProgress {
+    started_at: Option<u32>,
+    finished_at: Option<u32>,
+    next_spawn: u32,
+    spawned_creeps: u32,
+    random: Random,
 }
 
 pub struct Spawner {
-    pub pos: FloatPosition,
-    pub spawn: Spawn,
+    pub wave: Wave,
     ticks: u32,
-    last_spawn: u32,
-    spawned_creeps: u32,
+    progress: Vec<SpawnProgress>,
 }
 
 impl Spawner {
-    pub fn new(pos: FloatPosition, spawn: Spawn) -> Spawner {
-        Spawner {
-            pos,
-            spawn,
+    pub fn new(wave: Wave) -> Spawner {
+        let mut spawner = Spawner {
+            wave,
             ticks: 0,
-            last_spawn: 0,
-            spawned_creeps: 0,
+            progress: vec![],
+        };
+        spawner.reset();
+        spawner
+    }
+
+    // starts spawns whose predecessor has started (parallel) or finished
+    fn start_spawns(&mut self) {
+        for i in 0..self.wave.spawns.len() {
+            if self.progress[i].started_at.is_some() {
+                continue;
+            }
+
+            let spawn = &self.wave.spawns[i];
+            let start = if i == 0 {
+                Some(0)
+            } else if spawn.parallel {
+                self.progress[i - 1].started_at
+            } else {
+                self.progress[i - 1].finished_at
+            };
+
+            if let Some(start) = start {
+                let progress = &mut self.progress[i];
+                progress.started_at = Some(start);
+                progress.next_spawn = start
+                    + spawn.delay_in_ticks
+                    + spawn.distance_in_ticks(0, &mut progress.random)
+                    + 1;
+                if spawn.quantity == 0 {
+                    progress.finished_at = Some(start);
+                }
+            }
         }
     }
 
-    pub fn tick(&mut self) -> Option<Creep> {
+    pub fn tick(&mut self, state: &State) -> Vec<Creep> {
         self.ticks += 1;
+        self.start_spawns();
 
-        if (self.ticks - self.last_spawn > self.spawn.distance_in_ticks) && (!self.is_finished()) {
-            self.last_spawn = self.ticks;
-            self.spawned_creeps += 1;
+        let mut creeps = vec![];
+        for (spawn, progress) in self.wave.spawns.iter().zip(self.progress.iter_mut()) {
+            if progress.started_at.is_none()
+                || progress.finished_at.is_some()
+                || self.ticks < progress.next_spawn
+            {
+                continue;
+            }
 
-            let scaling = 1.0;
+            progress.spawned_creeps += 1;
+            progress.next_spawn = self.ticks
+                + spawn.distance_in_ticks(progress.spawned_creeps, &mut progress.random)
+                + 1;
+            if progress.spawned_creeps >= spawn.quantity {
+                progress.finished_at = Some(self.ticks);
+            }
 
-            return Some(Creep {
-                pos: self.pos,
-                health: self.spawn.health * scaling,
-                max_health: self.spawn.health * scaling,
+            // the flying path consists of the waypoints only
+            let (path, start) = match spawn.kind {
+                CreepKind::Flying => (
+                    &state.flying_path,
+                    u32::min(spawn.waypoint, state.creep_goals.len() as u32 - 1) as usize,
+                ),
+                _ => (&state.creep_path, state.waypoint_path_index(spawn.waypoint)),
+            };
+            creeps.push(Creep {
+                pos: path[start],
+                health: spawn.health,
+                max_health: spawn.health,
                 walking: WalkingProgress {
-                    current_goal: 0,
+                    current_goal: start as u32,
                     progress_made: 0.0,
                 },
-                speed: self.spawn.speed,
-                gold: self.spawn.bounty,
-                kind: self.spawn.kind,
+                speed: spawn.speed,
+                gold: spawn.bounty,
+                kind: spawn.kind,
                 last_freeze_percent: 0.0,
                 delta_speed: 0.0,
                 slow_speed_accumulated: 0.0,
                 max_freeze_percent: 0.0,
+                boss: match spawn.kind {
+                    CreepKind::Boss => Some(BossData::default()),
+                    _ => None,
+                },
+                shield: Shield::for_kind(spawn.kind, spawn.health),
+                visible: !matches!(spawn.kind, CreepKind::Stealth),
+                leak_damage: spawn.leak_damage,
+                poison: None,
+                ticks_alive: 0,
             });
         }
-        None
+
+        creeps.extend(self.drop_minions(state));
+        creeps
+    }
+
+    // carriers drop minions where they currently are, the minions follow the same path
+    fn drop_minions(&self, state: &State) -> Vec<Creep> {
+        state
+            .creeps
+            .iter()
+            .filter(|carrier| {
+                matches!(carrier.kind, CreepKind::Carrier)
+                    && carrier.ticks_alive > 0
+                    && carrier
+                        .ticks_alive
+                        .is_multiple_of(CARRIER_DROP_INTERVAL_IN_TICKS)
+                    && carrier.ticks_alive / CARRIER_DROP_INTERVAL_IN_TICKS <= CARRIER_MAX_DROPS
+            })
+            .map(|carrier| Creep {
+                health: carrier.max_health * CARRIER_MINION_HEALTH,
+                max_health: carrier.max_health * CARRIER_MINION_HEALTH,
+                gold: u32::max(1, carrier.gold / CARRIER_MAX_DROPS),
+                kind: CreepKind::Minion,
+                last_freeze_percent: 0.0,
+                max_freeze_percent: 0.0,
+                delta_speed: 0.0,
+                slow_speed_accumulated: 0.0,
+                leak_damage: CreepKind::Minion.default_leak_damage(),
+                poison: None,
+                ticks_alive: 0,
+                ..*carrier
+            })
+            .collect()
     }
 
     pub fn is_finished(&self) -> bool {
-        self.spawned_creeps >= self.spawn.quantity
+        self.progress
+            .iter()
+            .all(|progress| progress.finished_at.is_some())
     }
 
     pub fn reset(&mut self) {
         self.ticks = 0;
-        self.last_spawn = 0;
-        self.spawned_creeps = 0;
+        self.progress = self
+            .wave
+            .spawns
+            .iter()
+            .map(|spawn| SpawnProgress {
+                started_at: None,
+                finished_at: None,
+                next_spawn: 0,
+                spawned_creeps: 0,
+                random: Random::new(match spawn.pattern {
+                    SpawnPattern::Jitter { seed, .. } => seed,
+                    _ => 0,
+                }),
+            })
+            .collect();
     }
 
-    pub fn set_spawn(&mut self, spawn: Spawn) {
-        self.spawn = spawn;
+    pub fn set_wave(&mut self, wave: Wave) {
+        self.wave = wave;
+        self.reset();
     }
 }
+
+#[test]
+fn test_spawner_groups() {
+    let game = crate::Game::new();
+    let spawn = Spawn {
+        quantity: 2,
+        distance_in_ticks: 10,
+        delay_in_ticks: 0,
+        pattern: SpawnPattern::Fixed,
+        parallel: false,
+        health: 10.0,
+        speed: 1.0 / 60.0,
+        bounty: 1,
+        kind: CreepKind::Normal,
+        waypoint: 0,
+        leak_damage: 1.0,
+    };
+    let mut spawner = Spawner::new(Wave {
+        spawns: vec![
+            spawn.clone(),
+            Spawn {
+                kind: CreepKind::Big,
+                delay_in_ticks: 50,
+                waypoint: 1,
+                ..spawn.clone()
+            },
+            Spawn {
+                quantity: 6,
+                distance_in_ticks: 2,
+                pattern: SpawnPattern::Burst {
+                    size: 3,
+                    pause_in_ticks: 20,
+                },
+                parallel: true,
+                ..spawn
+            },
+        ],
+    });
+
+    let creeps: Vec<(u32, Creep)> = (1..=200)
+        .flat_map(|tick| {
+            spawner
+                .tick(&game.state)
+                .into_iter()
+                .map(move |creep| (tick, creep))
+        })
+        .collect();
+
+    let ticks: Vec<u32> = creeps.iter().map(|(tick, _)| *tick).collect();
+    assert_eq!(ticks, vec![11, 22, 25, 28, 31, 52, 55, 58, 83, 94]);
+    assert!(matches!(creeps[8].1.kind, CreepKind::Big));
+    assert_eq!(
+        creeps[8].1.walking.current_goal as usize,
+        game.state.waypoint_path_index(1)
+    );
+    assert!(game.state.waypoint_path_index(1) > 0);
+    assert!(spawner.is_finished());
+}
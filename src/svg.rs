@@ -0,0 +1,297 @@
+use std::cell::RefCell;
+
+use crate::art::Renderer;
+use crate::CreepKind;
+
+// keep in sync with web/src/js/Art.js
+const PARTICLE_SIZE: f32 = 5.0;
+const CREEP_SIZE: f32 = 20.0;
+const HEALTH_BAR_HEIGHT: f32 = 2.0;
+const TURRET_COLORS: [&str; 7] = ["yellow", "red", "cyan", "green", "blue", "lime", "magenta"];
+
+/// Renders the game into an svg document, e.g. for bug reports or golden-image tests.
+pub struct SvgRenderer {
+    width: RefCell<f32>,
+    height: RefCell<f32>,
+    elements: RefCell<Vec<String>>,
+    path: RefCell<Vec<(f32, f32)>>,
+    alpha: RefCell<f32>,
+}
+
+impl SvgRenderer {
+    pub fn new() -> Self {
+        SvgRenderer {
+            width: RefCell::new(0.0),
+            height: RefCell::new(0.0),
+            elements: RefCell::new(vec![]),
+            path: RefCell::new(vec![]),
+            alpha: RefCell::new(1.0),
+        }
+    }
+
+    pub fn to_svg(&self) -> String {
+        let width = *self.width.borrow();
+        let height = *self.height.borrow();
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+        );
+        svg.push_str(&format!(
+            "<rect x=\"0\" y=\"0\" width=\"{width}\" height=\"{height}\" fill=\"black\"/>\n"
+        ));
+        for element in self.elements.borrow().iter() {
+            svg.push_str(element);
+            svg.push('\n');
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn push(&self, element: String) {
+        let alpha = *self.alpha.borrow();
+        if alpha < 1.0 {
+            self.elements
+                .borrow_mut()
+                .push(format!("<g opacity=\"{alpha}\">{element}</g>"));
+        } else {
+            self.elements.borrow_mut().push(element);
+        }
+    }
+
+    fn fill_rect(&self, x: f32, y: f32, width: f32, height: f32, color: &str) {
+        self.push(format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" fill=\"{color}\"/>"
+        ));
+    }
+
+    fn stroke_rect(&self, x: f32, y: f32, width: f32, height: f32, color: &str, line_width: f32) {
+        self.push(format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"{line_width}\"/>"
+        ));
+    }
+
+    fn fill_circle(&self, x: f32, y: f32, r: f32, color: &str) {
+        self.push(format!(
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{r}\" fill=\"{color}\"/>"
+        ));
+    }
+
+    fn draw_line(&self, x: f32, y: f32, x2: f32, y2: f32, color: &str, line_width: f32) {
+        self.push(format!(
+            "<line x1=\"{x}\" y1=\"{y}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{color}\" stroke-width=\"{line_width}\"/>"
+        ));
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn draw_turret(&self, x: f32, y: f32, rotation: f32, size: f32, kind: i32) {
+        let color = TURRET_COLORS.get(kind as usize).unwrap_or(&"white");
+        self.stroke_rect(x + 2.0, y + 2.0, size - 4.0, size - 4.0, color, 3.0);
+        if kind == 4 {
+            self.fill_circle(x + size / 2.0, y + size / 2.0, size / 4.0, color);
+        } else {
+            let cannon_length = size / 2.0;
+            self.draw_line(
+                x + cannon_length,
+                y + cannon_length,
+                x + cannon_length * (1.0 + rotation.cos()),
+                y + cannon_length * (1.0 + rotation.sin()),
+                color,
+                3.0,
+            );
+        }
+    }
+
+    fn draw_particle(&self, x: f32, y: f32) {
+        self.fill_circle(x, y, PARTICLE_SIZE, "silver");
+    }
+
+    fn draw_cannon_particle(&self, x: f32, y: f32, r: f32) {
+        self.fill_circle(x, y, r, "orange");
+    }
+
+    fn draw_teleport_particle(&self, x: f32, y: f32, r: f32) {
+        self.push(format!(
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{r}\" fill=\"none\" stroke=\"violet\" stroke-width=\"2\"/>"
+        ));
+    }
+
+    fn draw_sniper_particle(&self, x: f32, y: f32, x2: f32, y2: f32) {
+        self.draw_line(x, y, x2, y2, "white", 1.0);
+    }
+
+    fn draw_laser_beam(&self, x: f32, y: f32, x2: f32, y2: f32, intensity: f32) {
+        self.draw_line(x, y, x2, y2, "magenta", 1.0 + 2.0 * intensity);
+    }
+
+    fn draw_creep(
+        &self,
+        x: f32,
+        y: f32,
+        health_percentage: f32,
+        shield_percentage: f32,
+        burrowed: bool,
+        kind: CreepKind,
+    ) {
+        if burrowed {
+            // only a small mound of dirt is visible
+            self.fill_rect(
+                x - CREEP_SIZE / 2.0,
+                y,
+                CREEP_SIZE,
+                CREEP_SIZE / 4.0,
+                "saddlebrown",
+            );
+        } else {
+            match kind {
+                CreepKind::Normal => self.fill_circle(x, y, CREEP_SIZE / 2.0, "green"),
+                CreepKind::Grouped => self.fill_circle(x, y, CREEP_SIZE / 4.0, "green"),
+                CreepKind::Speed => {
+                    let s = CREEP_SIZE / 2.0;
+                    self.push(format!(
+                        "<polygon points=\"{},{} {},{} {},{}\" fill=\"yellow\"/>",
+                        x - s,
+                        y + s,
+                        x,
+                        y - s,
+                        x + s,
+                        y + s
+                    ));
+                }
+                CreepKind::Big => self.fill_rect(
+                    x - CREEP_SIZE / 2.0,
+                    y - CREEP_SIZE / 2.0,
+                    CREEP_SIZE,
+                    CREEP_SIZE,
+                    "red",
+                ),
+                CreepKind::Boss => self.fill_rect(
+                    x - CREEP_SIZE,
+                    y - CREEP_SIZE,
+                    CREEP_SIZE * 2.0,
+                    CREEP_SIZE * 2.0,
+                    "purple",
+                ),
+                CreepKind::Minion => self.fill_circle(x, y, CREEP_SIZE / 4.0, "purple"),
+                CreepKind::Healer => self.fill_circle(x, y, CREEP_SIZE / 2.0, "pink"),
+                CreepKind::Splitter => self.fill_rect(
+                    x - CREEP_SIZE / 2.0,
+                    y - CREEP_SIZE / 2.0,
+                    CREEP_SIZE,
+                    CREEP_SIZE,
+                    "orange",
+                ),
+                CreepKind::Splitling => self.fill_circle(x, y, CREEP_SIZE / 4.0, "orange"),
+                CreepKind::Shielded => self.fill_circle(x, y, CREEP_SIZE / 2.0, "teal"),
+                CreepKind::Stealth => self.fill_circle(x, y, CREEP_SIZE / 2.0, "gray"),
+                CreepKind::Flying => self.fill_circle(x, y, CREEP_SIZE / 3.0, "skyblue"),
+                CreepKind::Emp => self.fill_circle(x, y, CREEP_SIZE / 2.0, "yellow"),
+                CreepKind::Enraging => self.fill_circle(x, y, CREEP_SIZE / 2.0, "crimson"),
+                CreepKind::Carrier => self.fill_rect(
+                    x - CREEP_SIZE / 2.0,
+                    y - CREEP_SIZE / 2.0,
+                    CREEP_SIZE,
+                    CREEP_SIZE,
+                    "sienna",
+                ),
+                CreepKind::Burrowing => self.fill_circle(x, y, CREEP_SIZE / 2.0, "peru"),
+                CreepKind::Teleporting => self.fill_circle(x, y, CREEP_SIZE / 2.0, "violet"),
+            }
+        }
+
+        self.fill_rect(
+            x - CREEP_SIZE / 2.0,
+            y - CREEP_SIZE / 2.0 - HEALTH_BAR_HEIGHT,
+            CREEP_SIZE * health_percentage,
+            HEALTH_BAR_HEIGHT,
+            "green",
+        );
+        if shield_percentage > 0.0 {
+            self.fill_rect(
+                x - CREEP_SIZE / 2.0,
+                y - CREEP_SIZE / 2.0 - 2.0 * HEALTH_BAR_HEIGHT,
+                CREEP_SIZE * shield_percentage,
+                HEALTH_BAR_HEIGHT,
+                "aqua",
+            );
+        }
+    }
+
+    fn draw_map(&self, width: f32, height: f32) {
+        *self.width.borrow_mut() = width;
+        *self.height.borrow_mut() = height;
+        self.stroke_rect(0.0, 0.0, width, height, "white", 1.0);
+    }
+
+    fn set_alpha(&self, alpha: f32) {
+        *self.alpha.borrow_mut() = alpha;
+    }
+
+    fn start_creep_path(&self, x: f32, y: f32, _time: f32) {
+        let mut path = self.path.borrow_mut();
+        path.clear();
+        path.push((x, y));
+    }
+
+    fn draw_creep_path_line(&self, x: f32, y: f32) {
+        self.path.borrow_mut().push((x, y));
+    }
+
+    fn end_creep_path(&self) {
+        let points: Vec<String> = self
+            .path
+            .borrow()
+            .iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect();
+        self.push(format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"white\" stroke-dasharray=\"3 5\"/>",
+            points.join(" ")
+        ));
+    }
+
+    fn draw_creep_spawn(&self, x: f32, y: f32, size: f32) {
+        self.fill_rect(x, y, size, size, "rgba(0, 255, 0, 0.3)");
+    }
+
+    fn draw_creep_goal(&self, x: f32, y: f32, size: f32) {
+        self.fill_rect(x, y, size, size, "rgba(255, 0, 0, 0.3)");
+    }
+
+    fn clear(&self) {
+        self.elements.borrow_mut().clear();
+        self.path.borrow_mut().clear();
+    }
+
+    fn draw_turret_range(&self, x: f32, y: f32, range: f32) {
+        self.push(format!(
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{range}\" fill=\"none\" stroke=\"white\" stroke-opacity=\"0.3\"/>"
+        ));
+    }
+}
+
+#[test]
+fn test_svg_renderer() {
+    let svg = SvgRenderer::new();
+    svg.clear();
+    svg.draw_map(300.0, 200.0);
+    svg.start_creep_path(15.0, 15.0, 0.0);
+    svg.draw_creep_path_line(15.0, 45.0);
+    svg.end_creep_path();
+    svg.draw_turret(30.0, 30.0, std::f32::consts::FRAC_PI_2, 30.0, 0);
+    svg.draw_creep(15.0, 45.0, 0.5, 0.0, false, CreepKind::Big);
+
+    let document = svg.to_svg();
+    assert!(document.starts_with("<svg"));
+    assert!(document.contains("viewBox=\"0 0 300 200\""));
+    assert!(document.contains("<polyline points=\"15,15 15,45\""));
+    assert!(document.contains("stroke=\"yellow\""));
+    assert!(document.contains("fill=\"red\""));
+    assert!(document.trim_end().ends_with("</svg>"));
+}
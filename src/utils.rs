@@ -83,6 +83,37 @@ pub fn to_grid_position(pos: FloatPosition, factor: f32) -> GridPosition {
     }
 }
 
+// deterministic xorshift random numbers, the same seed gives the same sequence everywhere
+#[derive(Clone, Copy)]
+pub struct Random {
+    state: u32,
+}
+
+impl Random {
+    pub fn new(seed: u32) -> Self {
+        Random {
+            state: u32::max(seed, 1),
+        }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    // uniformly distributed in [0, max]
+    pub fn next_up_to(&mut self, max: u32) -> u32 {
+        if max == u32::MAX {
+            return self.next_u32();
+        }
+        self.next_u32() % (max + 1)
+    }
+}
+
 pub fn set_panic_hook() {
     // When the `console_error_panic_hook` feature is enabled, we can call the
     // `set_panic_hook` function at least once during initialization, and then
@@ -14,7 +14,15 @@ export class Art {
   }
 
   drawTurret(x, y, rotation, size, type) {
-    let turretColor = ["yellow", "red", "cyan", "green", "blue"][type];
+    let turretColor = [
+      "yellow",
+      "red",
+      "cyan",
+      "green",
+      "blue",
+      "lime",
+      "magenta",
+    ][type];
     const ctx = this.canvas.canvas.getContext("2d");
     let lineWidth = ctx.lineWidth;
     ctx.lineWidth = 3;
@@ -47,12 +55,33 @@ export class Art {
     this.canvas.fillCircle(x, y, r, "orange");
   }
 
+  drawTeleportParticle(x, y, r) {
+    this.canvas.strokeCircle(x, y, r, "violet");
+  }
+
   drawSniperParticle(x, y, x2, y2) {
     this.canvas.drawLine(x, y, x2, y2, "white");
   }
 
-  drawCreep(x, y, healthPercentage, kind) {
-    if (kind === CreepKind.Normal) {
+  drawLaserBeam(x, y, x2, y2, intensity) {
+    const ctx = this.canvas.canvas.getContext("2d");
+    let lineWidth = ctx.lineWidth;
+    ctx.lineWidth = 1 + 2 * intensity;
+    this.canvas.drawLine(x, y, x2, y2, "magenta");
+    ctx.lineWidth = lineWidth;
+  }
+
+  drawCreep(x, y, healthPercentage, shieldPercentage, burrowed, kind) {
+    if (burrowed) {
+      // only a small mound of dirt is visible
+      this.canvas.fillRect(
+        x - CREEP_SIZE / 2.0,
+        y,
+        CREEP_SIZE,
+        CREEP_SIZE / 4.0,
+        "saddlebrown"
+      );
+    } else if (kind === CreepKind.Normal) {
       this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "green");
     } else if (kind === CreepKind.Grouped) {
       this.canvas.fillCircle(x, y, CREEP_SIZE / 4.0, "green");
@@ -66,6 +95,50 @@ export class Art {
         CREEP_SIZE,
         "red"
       );
+    } else if (kind === CreepKind.Boss) {
+      this.canvas.fillRect(
+        x - CREEP_SIZE,
+        y - CREEP_SIZE,
+        CREEP_SIZE * 2.0,
+        CREEP_SIZE * 2.0,
+        "purple"
+      );
+    } else if (kind === CreepKind.Minion) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 4.0, "purple");
+    } else if (kind === CreepKind.Healer) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "pink");
+    } else if (kind === CreepKind.Splitter) {
+      this.canvas.fillRect(
+        x - CREEP_SIZE / 2.0,
+        y - CREEP_SIZE / 2.0,
+        CREEP_SIZE,
+        CREEP_SIZE,
+        "orange"
+      );
+    } else if (kind === CreepKind.Splitling) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 4.0, "orange");
+    } else if (kind === CreepKind.Shielded) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "teal");
+    } else if (kind === CreepKind.Stealth) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "gray");
+    } else if (kind === CreepKind.Flying) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 3.0, "skyblue");
+    } else if (kind === CreepKind.Emp) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "yellow");
+    } else if (kind === CreepKind.Enraging) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "crimson");
+    } else if (kind === CreepKind.Carrier) {
+      this.canvas.fillRect(
+        x - CREEP_SIZE / 2.0,
+        y - CREEP_SIZE / 2.0,
+        CREEP_SIZE,
+        CREEP_SIZE,
+        "sienna"
+      );
+    } else if (kind === CreepKind.Burrowing) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "peru");
+    } else if (kind === CreepKind.Teleporting) {
+      this.canvas.fillCircle(x, y, CREEP_SIZE / 2.0, "violet");
     } else {
       throw new Error(`Uninmplemented kreep kind '${kind}'...`);
     }
@@ -77,12 +150,25 @@ export class Art {
       HEALTH_BAR_HEIGHT,
       "green"
     );
+    if (shieldPercentage > 0) {
+      this.canvas.fillRect(
+        x - CREEP_SIZE / 2,
+        y - CREEP_SIZE / 2 - 2 * HEALTH_BAR_HEIGHT,
+        CREEP_SIZE * shieldPercentage,
+        HEALTH_BAR_HEIGHT,
+        "aqua"
+      );
+    }
   }
 
   drawMap(width, height) {
     this.canvas.strokeRect(0, 0, width, height, "white");
   }
 
+  setAlpha(alpha) {
+    this.canvas.setAlpha(alpha);
+  }
+
   startCreepPath(x, y, time) {
     this.canvas.startPath(x, y, "white", -time / 60);
   }
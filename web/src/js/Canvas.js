@@ -125,6 +125,10 @@ export class Canvas {
     this.ctx.setLineDash([]);
   }
 
+  setAlpha(alpha) {
+    this.ctx.globalAlpha = alpha;
+  }
+
   fillCircle(x, y, r, color) {
     this.ctx.fillStyle = color;
     this.ctx.beginPath();
@@ -138,6 +142,19 @@ export class Canvas {
     this.ctx.fill();
   }
 
+  strokeCircle(x, y, r, color) {
+    this.ctx.strokeStyle = color;
+    this.ctx.beginPath();
+    this.ctx.arc(
+      x / this.scale + this.offsetX,
+      y / this.scale + this.offsetY,
+      r / this.scale,
+      0,
+      2 * Math.PI
+    );
+    this.ctx.stroke();
+  }
+
   fillText(x, y, text, color, fontSize) {
     this.ctx.fillStyle = color;
     this.ctx.font = `bold ${fontSize}px Courier`;
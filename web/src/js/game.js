@@ -1,4 +1,8 @@
-import { ExternalState, GameResult } from "../wasm/oxidized_turret_bg.js";
+import {
+  ExternalState,
+  GameResult,
+  StatId,
+} from "../wasm/oxidized_turret_bg.js";
 import { Canvas } from "./Canvas.js";
 import { Art } from "./Art.js";
 import { clamp } from "./utils.js";
@@ -34,7 +38,7 @@ export function createGameCanvas(htmlCanvas, initialCanvasState = {}) {
 
         if (uiState.upgrading) {
           const nextRange = uiState.selectedTower.data.next_stats.find(
-            (stat) => stat.key === "Range"
+            (stat) => stat.id === StatId.Range
           )?.value;
           if (nextRange) {
             canvas.fillCircle(
@@ -78,9 +78,14 @@ export async function initGame({ wasmPath, sendMessage, state = {} }) {
     speed: document.querySelector(".speed"),
     global: document.body,
     result: document.querySelector(".result"),
+    continueEndlessButton: document.querySelector(".continue-endless"),
     towerDetailSidebar: document.querySelector(".tower-detail-sidebar"),
     towerStats: document.querySelector(".tower-stats"),
     towerUpgradeButton: document.querySelector(".tower-upgrade"),
+    towerDetectorButton: document.querySelector(".tower-detector"),
+    towerTargetingButton: document.querySelector(".tower-targeting"),
+    towerCatalog: gameEngine.get_tower_catalog(),
+    detectorCost: gameEngine.get_detector_cost(),
   });
 
   const gameCanvas = createGameCanvas(
@@ -102,12 +107,18 @@ export async function initGame({ wasmPath, sendMessage, state = {} }) {
       stateHandler.decreaseAnimationSpeed();
     } else if (event.target.classList.contains("start")) {
       stateHandler.handleStartButton();
+    } else if (event.target.classList.contains("continue-endless")) {
+      stateHandler.handleContinueEndless();
     } else if (event.target.classList.contains("turret")) {
       console.log(event.target.dataset.type);
     } else if (event.target.classList.contains("tower-upgrade")) {
       stateHandler.handleTowerUpgrade();
     } else if (event.target.classList.contains("tower-sell")) {
       stateHandler.handleTowerSell();
+    } else if (event.target.classList.contains("tower-detector")) {
+      stateHandler.handleTowerDetector();
+    } else if (event.target.classList.contains("tower-targeting")) {
+      stateHandler.handleTowerTargeting();
     } else if (event.target.classList.contains("close")) {
       stateHandler.handleSidebarClose();
     }
@@ -122,6 +133,16 @@ export async function initGame({ wasmPath, sendMessage, state = {} }) {
       gameEngine.upgrade_tower(message.data.id, message.data.index);
     } else if (message.type === "sell_tower") {
       gameEngine.sell_tower(message.data.id, message.data.index);
+    } else if (message.type === "buy_detector") {
+      gameEngine.buy_detector(message.data.id, message.data.index);
+    } else if (message.type === "set_endless") {
+      gameEngine.set_endless(message.data.endless);
+    } else if (message.type === "set_targeting_mode") {
+      gameEngine.set_targeting_mode(
+        message.data.id,
+        message.data.index,
+        message.data.targeting
+      );
     }
   }
 
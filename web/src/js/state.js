@@ -68,6 +68,10 @@ export function createStateHandler({
           new_turret = 3;
         } else if (clickPos.y < 250) {
           new_turret = 4;
+        } else if (clickPos.y < 300) {
+          new_turret = 5;
+        } else if (clickPos.y < 350) {
+          new_turret = 6;
         }
 
         if (new_turret !== null) {
@@ -134,6 +138,8 @@ export function createStateHandler({
       uiState.wave = gameState.current_level;
       uiState.phase = gameState.phase;
       uiState.result = gameState.game_result;
+      uiState.endless = gameState.endless;
+      uiState.highestWave = gameState.highest_wave;
 
       gameEngine.draw_state(gameCanvas.getArt(), time);
       gameCanvas.drawState(gameState, uiState);
@@ -148,6 +154,9 @@ export function createStateHandler({
     handleStartButton() {
       sendMessage({ type: "start_wave" });
     },
+    handleContinueEndless() {
+      sendMessage({ type: "set_endless", data: { endless: true } });
+    },
     handleSidebarClose() {
       uiState.selectedTower = null;
       uiState.selectedTurret = null;
@@ -165,6 +174,43 @@ export function createStateHandler({
 
       uiState.selectedTower = null;
     },
+    handleTowerDetector() {
+      if (!uiState.selectedTower) {
+        return;
+      }
+      /** @type {wasm.RecycledListRef} */
+      const ref = uiState.selectedTower.turret_ref;
+      sendMessage({
+        type: "buy_detector",
+        data: { id: ref.id, index: ref.index },
+      });
+      uiState.selectedTower = gameEngine.get_tower_by_ref(
+        uiState.selectedTower.turret_ref
+      );
+    },
+    handleTowerTargeting() {
+      if (!uiState.selectedTower) {
+        return;
+      }
+      // cycles through the modes, the enum maps names and values both ways
+      const modes = Object.values(wasm.TargetingMode).filter(
+        (mode) => typeof mode === "number"
+      );
+      const targeting = uiState.selectedTower.data.turret.targeting;
+      /** @type {wasm.RecycledListRef} */
+      const ref = uiState.selectedTower.turret_ref;
+      sendMessage({
+        type: "set_targeting_mode",
+        data: {
+          id: ref.id,
+          index: ref.index,
+          targeting: modes[(modes.indexOf(targeting) + 1) % modes.length],
+        },
+      });
+      uiState.selectedTower = gameEngine.get_tower_by_ref(
+        uiState.selectedTower.turret_ref
+      );
+    },
     handleTowerUpgrade() {
       if (!uiState.selectedTower) {
         return;
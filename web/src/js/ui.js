@@ -4,9 +4,23 @@ import {
   TurretRef,
   GamePhase,
   GameResult,
+  Locale,
+  TargetingMode,
+  stat_label,
+  unit_label,
 } from "../wasm/oxidized_turret_bg.js";
 
-function drawBasicTurret(uiCanvas, uiArt, uiState) {
+function getLocale() {
+  const language = navigator.language.slice(0, 2);
+  if (language === "de") {
+    return Locale.De;
+  } else if (language === "fr") {
+    return Locale.Fr;
+  }
+  return Locale.En;
+}
+
+function drawBasicTurret(uiCanvas, uiArt, uiState, catalog) {
   // draw selection
   if (uiState.selectedTurret === 0) {
     uiCanvas.fillRect(0, 0, 50, 50, "green");
@@ -17,10 +31,10 @@ function drawBasicTurret(uiCanvas, uiArt, uiState) {
 
   // draw price
   uiCanvas.fillCircle(12, 40, 9, "gray");
-  uiCanvas.fillText(5, 44, "20", "black", 12);
+  uiCanvas.fillText(5, 44, `${catalog[0].base_cost}`, "black", 12);
 }
 
-function drawSniperTurret(uiCanvas, uiArt, uiState) {
+function drawSniperTurret(uiCanvas, uiArt, uiState, catalog) {
   // draw selection
   if (uiState.selectedTurret === 1) {
     uiCanvas.fillRect(0, 50, 50, 50, "green");
@@ -31,10 +45,10 @@ function drawSniperTurret(uiCanvas, uiArt, uiState) {
 
   // draw price
   uiCanvas.fillCircle(12, 90, 9, "gray");
-  uiCanvas.fillText(5, 94, "80", "black", 12);
+  uiCanvas.fillText(5, 94, `${catalog[1].base_cost}`, "black", 12);
 }
 
-function drawCannonTurret(uiCanvas, uiArt, uiState) {
+function drawCannonTurret(uiCanvas, uiArt, uiState, catalog) {
   // draw selection
   if (uiState.selectedTurret === 2) {
     uiCanvas.fillRect(0, 100, 50, 50, "green");
@@ -45,10 +59,10 @@ function drawCannonTurret(uiCanvas, uiArt, uiState) {
 
   // draw price
   uiCanvas.fillCircle(12, 140, 9, "gray");
-  uiCanvas.fillText(5, 144, "60", "black", 12);
+  uiCanvas.fillText(5, 144, `${catalog[2].base_cost}`, "black", 12);
 }
 
-function drawMultiTurret(uiCanvas, uiArt, uiState) {
+function drawMultiTurret(uiCanvas, uiArt, uiState, catalog) {
   // draw selection
   if (uiState.selectedTurret === 3) {
     uiCanvas.fillRect(0, 150, 50, 50, "green");
@@ -59,10 +73,10 @@ function drawMultiTurret(uiCanvas, uiArt, uiState) {
 
   // draw price
   uiCanvas.fillCircle(12, 190, 9, "gray");
-  uiCanvas.fillText(5, 194, "90", "black", 12);
+  uiCanvas.fillText(5, 194, `${catalog[3].base_cost}`, "black", 12);
 }
 
-function drawFreezeTurret(uiCanvas, uiArt, uiState) {
+function drawFreezeTurret(uiCanvas, uiArt, uiState, catalog) {
   // draw selection
   if (uiState.selectedTurret === 4) {
     uiCanvas.fillRect(0, 200, 50, 50, "green");
@@ -73,7 +87,35 @@ function drawFreezeTurret(uiCanvas, uiArt, uiState) {
 
   // draw price
   uiCanvas.fillCircle(12, 240, 9, "gray");
-  uiCanvas.fillText(5, 244, "80", "black", 12);
+  uiCanvas.fillText(5, 244, `${catalog[4].base_cost}`, "black", 12);
+}
+
+function drawPoisonTurret(uiCanvas, uiArt, uiState, catalog) {
+  // draw selection
+  if (uiState.selectedTurret === 5) {
+    uiCanvas.fillRect(0, 250, 50, 50, "green");
+  }
+
+  // draw icon
+  uiArt.drawTurret(10, 260, 90, 30, 5);
+
+  // draw price
+  uiCanvas.fillCircle(12, 290, 9, "gray");
+  uiCanvas.fillText(5, 294, `${catalog[5].base_cost}`, "black", 12);
+}
+
+function drawLaserTurret(uiCanvas, uiArt, uiState, catalog) {
+  // draw selection
+  if (uiState.selectedTurret === 6) {
+    uiCanvas.fillRect(0, 300, 50, 50, "green");
+  }
+
+  // draw icon
+  uiArt.drawTurret(10, 310, 90, 30, 6);
+
+  // draw price
+  uiCanvas.fillCircle(12, 340, 9, "gray");
+  uiCanvas.fillText(5, 344, `${catalog[6].base_cost}`, "black", 12);
 }
 
 function round(num) {
@@ -88,22 +130,30 @@ export function createUi({
   speed,
   global,
   result,
+  continueEndlessButton,
   towerDetailSidebar,
   towerStats,
   towerUpgradeButton,
+  towerDetectorButton,
+  towerTargetingButton,
+  towerCatalog,
+  detectorCost,
 }) {
   const uiCanvas = new Canvas(canvas);
   const uiArt = new Art(uiCanvas);
+  const locale = getLocale();
 
   return {
     drawUi(uiState) {
       // draw background
       uiCanvas.fillRect(0, 0, 50, uiCanvas.getSize().height, "#222222");
-      drawBasicTurret(uiCanvas, uiArt, uiState);
-      drawSniperTurret(uiCanvas, uiArt, uiState);
-      drawCannonTurret(uiCanvas, uiArt, uiState);
-      drawMultiTurret(uiCanvas, uiArt, uiState);
-      drawFreezeTurret(uiCanvas, uiArt, uiState);
+      drawBasicTurret(uiCanvas, uiArt, uiState, towerCatalog);
+      drawSniperTurret(uiCanvas, uiArt, uiState, towerCatalog);
+      drawCannonTurret(uiCanvas, uiArt, uiState, towerCatalog);
+      drawMultiTurret(uiCanvas, uiArt, uiState, towerCatalog);
+      drawFreezeTurret(uiCanvas, uiArt, uiState, towerCatalog);
+      drawPoisonTurret(uiCanvas, uiArt, uiState, towerCatalog);
+      drawLaserTurret(uiCanvas, uiArt, uiState, towerCatalog);
 
       if (uiState.selectedTower) {
         /** @type {TurretRef} */
@@ -116,6 +166,27 @@ export function createUi({
           towerUpgradeButton.style.display = "none";
         }
 
+        // detectors reveal stealth creeps in the range of the tower, they can only be bought while building
+        if (
+          turret.data.turret.detector ||
+          uiState.phase !== GamePhase.Building
+        ) {
+          towerDetectorButton.style.display = "none";
+        } else {
+          towerDetectorButton.style.display = "inline-block";
+        }
+        towerDetectorButton.innerText = `Buy detector (${detectorCost})`;
+
+        // freeze towers slow everything in range and have no target
+        if (turret.data.turret.kind === 4) {
+          towerTargetingButton.style.display = "none";
+        } else {
+          towerTargetingButton.style.display = "inline-block";
+        }
+        towerTargetingButton.innerText = `Target: ${
+          TargetingMode[turret.data.turret.targeting]
+        }`;
+
         if (uiState.upgrading) {
           towerUpgradeButton.innerText = "Buy Upgrade!";
         } else {
@@ -132,7 +203,10 @@ export function createUi({
           ${turret.data.stats
             .map((stat, index) => {
               const next = turret.data.next_stats[index];
-              return `<tr><td>${stat.key} ${stat.unit}</td><td>${round(
+              return `<tr><td>${stat_label(stat.id, locale)} ${unit_label(
+                stat.unit,
+                locale
+              )}</td><td>${round(
                 stat.value
               )}</td><td>${round(next.value)}</td></tr>`;
             })
@@ -145,9 +219,9 @@ export function createUi({
           ${turret.data.stats
             .map(
               (stat) =>
-                `<tr><td>${stat.key}</td><td>${round(stat.value)} ${
-                  stat.unit
-                }</td></tr>`
+                `<tr><td>${stat_label(stat.id, locale)}</td><td>${round(
+                  stat.value
+                )} ${unit_label(stat.unit, locale)}</td></tr>`
             )
             .join("")}
           </table>`;
@@ -155,6 +229,8 @@ export function createUi({
 
         towerStats.innerHTML = `
           <div>Selected Tower (id): ${turret.turret_ref.id}</div>
+          <div>Detector: ${turret.data.turret.detector ? "yes" : "no"}</div>
+          <div>Targeting: ${TargetingMode[turret.data.turret.targeting]}</div>
           ${towerStatsTable}
         `;
       } else {
@@ -167,7 +243,11 @@ export function createUi({
       speed.innerText = `Speed: ${uiState.animationSpeed}`;
 
       global.className = "";
-      if (uiState.phase === GamePhase.Building) {
+      // a won game waits in the building phase until it goes on in endless mode
+      if (
+        uiState.phase === GamePhase.Building &&
+        uiState.result === GameResult.StillRunning
+      ) {
         global.classList.add("building");
       } else {
         global.classList.add("fighting");
@@ -178,16 +258,21 @@ export function createUi({
           result.style.display = "block";
           result.classList.add("won");
           result.innerText = `You won with ${uiState.health} hp!`;
+          continueEndlessButton.style.display = "block";
           break;
         }
         case GameResult.CreepsWon: {
           result.style.display = "block";
+          continueEndlessButton.style.display = "none";
           result.classList.add("lost");
-          result.innerText = `You lost at level ${uiState.wave}!`;
+          result.innerText = uiState.endless
+            ? `You reached wave ${uiState.highestWave}!`
+            : `You lost at level ${uiState.wave}!`;
           break;
         }
         case GameResult.StillRunning: {
           result.style.display = "none";
+          continueEndlessButton.style.display = "none";
           break;
         }
       }